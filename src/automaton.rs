@@ -0,0 +1,244 @@
+use std::{collections::HashMap, hash::Hash, thread};
+
+use crate::Grid;
+
+/// Which cells count as neighbors when applying an automaton rule
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    #[default]
+    Four,
+    Eight,
+}
+
+/// Steps a [`Grid`] through generations of a cellular automaton. The next
+/// generation is written into a second buffer that is swapped with the
+/// current one, so stepping never reallocates.
+#[derive(Debug, Clone)]
+pub struct Automaton<T> {
+    grid: Grid<T>,
+    next: Grid<T>,
+    neighborhood: Neighborhood,
+    generation: usize,
+}
+
+/// Compute the next value of cell `i` by handing the rule the cell and its
+/// neighbors
+fn apply_rule<T, F>(
+    grid: &Grid<T>,
+    neighborhood: Neighborhood,
+    i: usize,
+    rule: &F,
+) -> T
+where
+    F: Fn(&T, &[&T]) -> T,
+{
+    let cell = &grid.data[i];
+    let mut neighbors = [cell; 8];
+    let mut count = 0;
+
+    let mut push = |j: usize| {
+        neighbors[count] = &grid.data[j];
+        count += 1;
+    };
+
+    match neighborhood {
+        Neighborhood::Four => grid.neighbors(i).for_each(|(_, j)| push(j)),
+        Neighborhood::Eight => grid.neighbors8(i).for_each(|(_, j)| push(j)),
+    }
+
+    rule(cell, &neighbors[..count])
+}
+
+/// Fill a band of rows of the next generation, each tagged with its row
+/// number
+fn fill_rows<T, F>(
+    grid: &Grid<T>,
+    neighborhood: Neighborhood,
+    rule: &F,
+    rows: &mut [(usize, &mut [T])],
+) where
+    F: Fn(&T, &[&T]) -> T,
+{
+    for (row, cells) in rows {
+        for (col, cell) in cells.iter_mut().enumerate() {
+            let i = *row * grid.width + col;
+            *cell = apply_rule(grid, neighborhood, i, rule);
+        }
+    }
+}
+
+impl<T> Automaton<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(grid: Grid<T>, neighborhood: Neighborhood) -> Self {
+        Self {
+            next: grid.clone(),
+            grid,
+            neighborhood,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Swap in the freshly computed generation and report whether any cell
+    /// changed
+    fn swap_buffers(&mut self) -> bool {
+        let changed = self.grid.data != self.next.data;
+        std::mem::swap(&mut self.grid.data, &mut self.next.data);
+        self.generation += 1;
+        changed
+    }
+
+    /// Advance one generation. `rule` gets each cell and the values of its
+    /// neighbors and returns the cell's next value. Returns true if anything
+    /// changed
+    pub fn step(&mut self, rule: impl Fn(&T, &[&T]) -> T) -> bool {
+        for (i, next) in self.next.data.iter_mut().enumerate() {
+            *next = apply_rule(&self.grid, self.neighborhood, i, &rule);
+        }
+
+        self.swap_buffers()
+    }
+
+    /// Same as [`Automaton::step`] but splits the rows of the next generation
+    /// into one band per available core. The calling thread fills the first
+    /// band itself, so grids too small to split never spawn a thread
+    pub fn step_parallel(
+        &mut self,
+        rule: impl Fn(&T, &[&T]) -> T + Sync,
+    ) -> bool
+    where
+        T: Send + Sync,
+    {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let rows_per_band = self.grid.height.div_ceil(threads).max(1);
+
+        let grid = &self.grid;
+        let neighborhood = self.neighborhood;
+        let rule = &rule;
+        let mut rows = self.next.rows_mut().enumerate().collect::<Vec<_>>();
+
+        thread::scope(|scope| {
+            let mut bands = rows.chunks_mut(rows_per_band);
+            let first = bands.next();
+
+            for band in bands {
+                scope.spawn(move || fill_rows(grid, neighborhood, rule, band));
+            }
+
+            if let Some(band) = first {
+                fill_rows(grid, neighborhood, rule, band);
+            }
+        });
+
+        self.swap_buffers()
+    }
+
+    /// Keep stepping until a generation leaves the grid unchanged. Returns
+    /// the number of generations stepped, including the final one that
+    /// changed nothing
+    pub fn run_until_stable(&mut self, rule: impl Fn(&T, &[&T]) -> T) -> usize {
+        let start = self.generation;
+        while self.step(&rule) {}
+        self.generation - start
+    }
+}
+
+impl<T> Automaton<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Advance to the given generation. Every state seen along the way is
+    /// remembered so once the automaton repeats itself we can jump straight
+    /// to the answer, which makes "after a billion steps" questions cheap
+    pub fn advance_to(
+        &mut self,
+        generation: usize,
+        rule: impl Fn(&T, &[&T]) -> T,
+    ) {
+        // Each state seen, keyed to the step it was seen at
+        let mut seen = HashMap::new();
+
+        while self.generation < generation {
+            if let Some(&first) = seen.get(&self.grid.data) {
+                let period = seen.len() - first;
+                let target = first + (generation - self.generation) % period;
+
+                self.grid.data = seen
+                    .into_iter()
+                    .find_map(|(state, step)| (step == target).then_some(state))
+                    .unwrap();
+                self.generation = generation;
+                return;
+            }
+
+            seen.insert(self.grid.data.clone(), seen.len());
+            self.step(&rule);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Automaton, Neighborhood};
+    use crate::Grid;
+
+    fn life(cell: &char, neighbors: &[&char]) -> char {
+        match (*cell, neighbors.iter().filter(|c| ***c == '#').count()) {
+            ('#', 2 | 3) | ('.', 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn blinker_cycles() {
+        let start = Grid::parse_lines(".....\n..#..\n..#..\n..#..\n.....");
+        let mut automaton = Automaton::new(start.clone(), Neighborhood::Eight);
+
+        assert!(automaton.step(life));
+        assert_eq!(automaton.grid(), &start.rotate_cw());
+        assert!(automaton.step_parallel(life));
+        assert_eq!(automaton.grid(), &start);
+
+        automaton.advance_to(1_000_000_001, life);
+        assert_eq!(automaton.generation(), 1_000_000_001);
+        assert_eq!(automaton.grid(), &start.rotate_cw());
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        let data = (0..40 * 40)
+            .map(|i| if i % 5 == 0 || i % 11 == 0 { '#' } else { '.' })
+            .collect();
+        let start = Grid::new(data, 40);
+
+        let mut serial = Automaton::new(start.clone(), Neighborhood::Eight);
+        let mut parallel = Automaton::new(start, Neighborhood::Eight);
+
+        for _ in 0..5 {
+            assert_eq!(serial.step(life), parallel.step_parallel(life));
+            assert_eq!(serial.grid(), parallel.grid());
+        }
+    }
+
+    #[test]
+    fn block_is_stable() {
+        let start = Grid::parse_lines("....\n.##.\n.##.\n....");
+        let mut automaton = Automaton::new(start.clone(), Neighborhood::Eight);
+
+        assert_eq!(automaton.run_until_stable(life), 1);
+        assert_eq!(automaton.into_grid(), start);
+    }
+}
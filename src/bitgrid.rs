@@ -0,0 +1,359 @@
+use std::{
+    fmt::{self, Display},
+    ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index,
+        Not,
+    },
+};
+
+use strum::IntoEnumIterator;
+
+use crate::{Compass, Grid};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A bit-packed boolean grid. Cells are addressed with the same flat indices
+/// as a [`Grid`] of the same size, but every row is padded out to a whole
+/// number of words so rows can be shifted independently
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    pub width: usize,
+    pub height: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(BITS);
+
+        Self {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The word and bit that hold cell `i`
+    fn locate(&self, i: usize) -> (usize, u64) {
+        let (col, row) = (i % self.width, i / self.width);
+        (row * self.stride + col / BITS, 1 << (col % BITS))
+    }
+
+    /// Mask of the bits in the last word of each row that are real cells
+    fn tail_mask(&self) -> u64 {
+        match self.width % BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        let (word, bit) = self.locate(i);
+        self.words[word] & bit != 0
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        let (word, bit) = self.locate(i);
+
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    /// Set cell `i`, returning true if it wasn't already set
+    pub fn insert(&mut self, i: usize) -> bool {
+        let was_set = self.get(i);
+        self.set(i, true);
+        !was_set
+    }
+
+    /// Clear cell `i`, returning true if it was set
+    pub fn remove(&mut self, i: usize) -> bool {
+        let was_set = self.get(i);
+        self.set(i, false);
+        was_set
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        let (word, bit) = self.locate(i);
+        self.words[word] ^= bit;
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|w| *w != 0)
+    }
+
+    /// Flat indices of every set cell in order
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.chunks(self.stride.max(1)).enumerate().flat_map(
+            move |(row, words)| {
+                words.iter().enumerate().flat_map(move |(w, word)| {
+                    let mut bits = *word;
+
+                    std::iter::from_fn(move || {
+                        (bits != 0).then(|| {
+                            let bit = bits.trailing_zeros() as usize;
+                            bits &= bits - 1;
+                            row * self.width + w * BITS + bit
+                        })
+                    })
+                })
+            },
+        )
+    }
+
+    #[allow(clippy::unnecessary_lazy_evaluations)]
+    pub fn step_from_index(&self, i: usize, dir: Compass) -> Option<usize> {
+        use Compass as D;
+
+        let col = i % self.width;
+
+        match dir {
+            D::E => (col < (self.width - 1)).then_some(i + 1),
+            D::W => (col > 0).then(|| i - 1),
+            D::N => i.checked_sub(self.width),
+            D::S => Some(i + self.width).filter(|j| *j < self.len()),
+        }
+    }
+
+    pub fn neighbors(
+        &self,
+        i: usize,
+    ) -> impl Iterator<Item = (Compass, usize)> + '_ {
+        Compass::iter().filter_map(move |dir| {
+            self.step_from_index(i, dir).map(move |j| (dir, j))
+        })
+    }
+
+    pub fn escaping(&self, i: usize) -> impl Iterator<Item = Compass> + '_ {
+        Compass::iter()
+            .filter(move |dir| self.step_from_index(i, *dir).is_some())
+    }
+
+    /// Move every set cell one step in the given direction. Cells that would
+    /// leave the grid are dropped
+    pub fn shift(&self, dir: Compass) -> Self {
+        let mut result = Self::new(self.width, self.height);
+        let stride = self.stride;
+
+        match dir {
+            Compass::N => {
+                result.words[..self.words.len() - stride]
+                    .copy_from_slice(&self.words[stride..]);
+            }
+            Compass::S => {
+                result.words[stride..]
+                    .copy_from_slice(&self.words[..self.words.len() - stride]);
+            }
+            Compass::E => {
+                let mask = self.tail_mask();
+
+                for (from, to) in self
+                    .words
+                    .chunks(stride)
+                    .zip(result.words.chunks_mut(stride))
+                {
+                    let mut carry = 0;
+
+                    for (w, word) in from.iter().enumerate() {
+                        to[w] = (word << 1) | carry;
+                        carry = word >> (BITS - 1);
+                    }
+
+                    to[stride - 1] &= mask;
+                }
+            }
+            Compass::W => {
+                for (from, to) in self
+                    .words
+                    .chunks(stride)
+                    .zip(result.words.chunks_mut(stride))
+                {
+                    let mut carry = 0;
+
+                    for (w, word) in from.iter().enumerate().rev() {
+                        to[w] = (word >> 1) | carry;
+                        carry = word << (BITS - 1);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Convert back to a dense grid using `on` and `off` for set and unset
+    /// cells
+    pub fn to_grid<T: Clone>(&self, on: T, off: T) -> Grid<T> {
+        let data = (0..self.len())
+            .map(|i| if self.get(i) { on.clone() } else { off.clone() })
+            .collect();
+
+        Grid::new(data, self.width)
+    }
+}
+
+impl Index<usize> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        if self.get(i) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> Self::Output {
+        let mask = self.tail_mask();
+        let mut result = self.clone();
+
+        for row in result.words.chunks_mut(self.stride.max(1)) {
+            row.iter_mut().for_each(|w| *w = !*w);
+
+            if let Some(last) = row.last_mut() {
+                *last &= mask;
+            }
+        }
+
+        result
+    }
+}
+
+macro_rules! bit_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign:ident, $op:tt) => {
+        impl $assign_trait<&BitGrid> for BitGrid {
+            fn $assign(&mut self, rhs: &BitGrid) {
+                assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+
+                self.words
+                    .iter_mut()
+                    .zip(rhs.words.iter())
+                    .for_each(|(a, b)| *a = *a $op *b);
+            }
+        }
+
+        impl $trait for &BitGrid {
+            type Output = BitGrid;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                let mut result = self.clone();
+                $assign_trait::$assign(&mut result, rhs);
+                result
+            }
+        }
+    };
+}
+
+bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        grid.to_bit_grid(|t| *t)
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.height {
+            if row > 0 {
+                writeln!(f)?;
+            }
+
+            for col in 0..self.width {
+                let c = if self.get(row * self.width + col) {
+                    '#'
+                } else {
+                    '.'
+                };
+
+                write!(f, "{c}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Pack the cells matching the predicate into a [`BitGrid`]
+    pub fn to_bit_grid(&self, pred: impl Fn(&T) -> bool) -> BitGrid {
+        let mut result = BitGrid::new(self.width, self.height);
+
+        for (i, t) in self.data.iter().enumerate() {
+            if pred(t) {
+                result.set(i, true);
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{BitGrid, Compass, Grid};
+
+    #[test]
+    fn shifts_stay_within_rows() {
+        let width = 70;
+        let mut bits = BitGrid::new(width, 3);
+
+        bits.set(63, true);
+        bits.set(width - 1, true);
+        bits.set(width, true);
+
+        let east = bits.shift(Compass::E);
+        assert_eq!(east.iter_ones().collect::<Vec<_>>(), vec![64, width + 1]);
+
+        let west = bits.shift(Compass::W);
+        assert_eq!(west.iter_ones().collect::<Vec<_>>(), vec![62, width - 2]);
+
+        let south = bits.shift(Compass::S);
+        assert_eq!(
+            south.iter_ones().collect::<Vec<_>>(),
+            vec![width + 63, 2 * width - 1, 2 * width]
+        );
+        assert_eq!(south.shift(Compass::N), bits);
+    }
+
+    #[test]
+    fn bulk_ops() {
+        let grid = Grid::<char>::parse_lines("#.#\n.##");
+        let bits = grid.to_bit_grid(|c| *c == '#');
+
+        assert_eq!(bits.count_ones(), 4);
+        assert_eq!((!&bits).count_ones(), 2);
+        assert_eq!((&bits & &bits.shift(Compass::E)).count_ones(), 1);
+        assert_eq!((&bits ^ &bits).count_ones(), 0);
+        assert_eq!(bits.to_grid('#', '.'), grid);
+        assert_eq!(bits.to_string(), "#.#\n.##");
+    }
+}
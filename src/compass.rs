@@ -0,0 +1,464 @@
+use strum::{EnumIter, IntoEnumIterator};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Compass {
+    #[default]
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Compass {
+    pub fn from_relative(relative: char) -> Option<Self> {
+        match relative {
+            'U' => Some(Compass::N),
+            'D' => Some(Compass::S),
+            'L' => Some(Compass::W),
+            'R' => Some(Compass::E),
+            _ => None,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        use Compass as D;
+
+        match self {
+            D::E => D::W,
+            D::N => D::S,
+            D::W => D::E,
+            D::S => D::N,
+        }
+    }
+
+    pub fn from_arrow(arrow: char) -> Option<Self> {
+        match arrow {
+            '^' => Some(Compass::N),
+            'v' => Some(Compass::S),
+            '<' => Some(Compass::W),
+            '>' => Some(Compass::E),
+            _ => None,
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'N' => Some(Compass::N),
+            'E' => Some(Compass::E),
+            'S' => Some(Compass::S),
+            'W' => Some(Compass::W),
+            _ => None,
+        }
+    }
+
+    /// Accepts any of the arrow (`^v<>`), letter (`NESW`) or relative
+    /// (`UDLR`) notations
+    pub fn from_char(c: char) -> Option<Self> {
+        Self::from_arrow(c)
+            .or_else(|| Self::from_letter(c))
+            .or_else(|| Self::from_relative(c))
+    }
+
+    pub fn arrow(&self) -> char {
+        match self {
+            Compass::N => '^',
+            Compass::E => '>',
+            Compass::S => 'v',
+            Compass::W => '<',
+        }
+    }
+
+    /// The `(dx, dy)` of one step in this direction with y growing downward
+    /// the same way grid rows do
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Compass::N => (0, -1),
+            Compass::E => (1, 0),
+            Compass::S => (0, 1),
+            Compass::W => (-1, 0),
+        }
+    }
+
+    /// Number of clockwise quarter turns from north
+    pub fn quarter_turns(&self) -> u8 {
+        match self {
+            Compass::N => 0,
+            Compass::E => 1,
+            Compass::S => 2,
+            Compass::W => 3,
+        }
+    }
+
+    pub fn from_quarter_turns(turns: i32) -> Self {
+        match turns.rem_euclid(4) {
+            0 => Compass::N,
+            1 => Compass::E,
+            2 => Compass::S,
+            _ => Compass::W,
+        }
+    }
+
+    /// Clockwise angle from north in degrees
+    pub fn degrees(&self) -> u32 {
+        self.quarter_turns() as u32 * 90
+    }
+
+    /// The direction at the given clockwise angle from north if it's a
+    /// multiple of 90 degrees
+    pub fn from_degrees(degrees: i32) -> Option<Self> {
+        (degrees % 90 == 0).then(|| Self::from_quarter_turns(degrees / 90))
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.turn(Turn::Left)
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.turn(Turn::Right)
+    }
+
+    pub fn turn(&self, turn: Turn) -> Self {
+        Self::from_quarter_turns(
+            self.quarter_turns() as i32 + turn.quarter_turns() as i32,
+        )
+    }
+
+    /// The turn that takes you from facing `self` to facing `to`
+    pub fn turn_to(&self, to: Compass) -> Turn {
+        Turn::from_quarter_turns(
+            to.quarter_turns() as i32 - self.quarter_turns() as i32,
+        )
+    }
+}
+
+impl TryFrom<char> for Compass {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or(c)
+    }
+}
+
+/// A change of heading relative to the current one
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Turn {
+    #[default]
+    Straight,
+    Right,
+    Back,
+    Left,
+}
+
+impl Turn {
+    /// Number of clockwise quarter turns this represents
+    pub fn quarter_turns(&self) -> u8 {
+        match self {
+            Turn::Straight => 0,
+            Turn::Right => 1,
+            Turn::Back => 2,
+            Turn::Left => 3,
+        }
+    }
+
+    pub fn from_quarter_turns(turns: i32) -> Self {
+        match turns.rem_euclid(4) {
+            0 => Turn::Straight,
+            1 => Turn::Right,
+            2 => Turn::Back,
+            _ => Turn::Left,
+        }
+    }
+
+    /// Parse `L`/`R`/`S`/`B` style turn instructions
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'L' => Some(Turn::Left),
+            'R' => Some(Turn::Right),
+            'S' | 'F' => Some(Turn::Straight),
+            'B' => Some(Turn::Back),
+            _ => None,
+        }
+    }
+
+    /// The single turn equivalent to making this turn and then `next`
+    pub fn then(&self, next: Turn) -> Self {
+        Self::from_quarter_turns(
+            self.quarter_turns() as i32 + next.quarter_turns() as i32,
+        )
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::from_quarter_turns(-(self.quarter_turns() as i32))
+    }
+}
+
+/// A compact set of [`Compass`] directions, e.g. for tracking which headings
+/// a cell has been visited with in a `Grid<DirSet>`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirSet(u8);
+
+impl DirSet {
+    pub const EMPTY: DirSet = DirSet(0);
+    pub const ALL: DirSet = DirSet(0b1111);
+
+    fn bit(dir: Compass) -> u8 {
+        1 << dir.quarter_turns()
+    }
+
+    pub fn contains(&self, dir: Compass) -> bool {
+        self.0 & Self::bit(dir) != 0
+    }
+
+    /// Add the direction, returning true if it wasn't already present
+    pub fn insert(&mut self, dir: Compass) -> bool {
+        let added = !self.contains(dir);
+        self.0 |= Self::bit(dir);
+        added
+    }
+
+    /// Remove the direction, returning true if it was present
+    pub fn remove(&mut self, dir: Compass) -> bool {
+        let removed = self.contains(dir);
+        self.0 &= !Self::bit(dir);
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(&self, other: DirSet) -> DirSet {
+        DirSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: DirSet) -> DirSet {
+        DirSet(self.0 & other.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Compass> + '_ {
+        Compass::iter().filter(|dir| self.contains(*dir))
+    }
+}
+
+impl FromIterator<Compass> for DirSet {
+    fn from_iter<I: IntoIterator<Item = Compass>>(iter: I) -> Self {
+        let mut result = DirSet::EMPTY;
+        iter.into_iter().for_each(|dir| {
+            result.insert(dir);
+        });
+        result
+    }
+}
+
+impl From<Compass> for DirSet {
+    fn from(dir: Compass) -> Self {
+        DirSet(Self::bit(dir))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Compass8 {
+    #[default]
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Compass8 {
+    pub fn opposite(&self) -> Self {
+        use Compass8 as D;
+
+        match self {
+            D::N => D::S,
+            D::NE => D::SW,
+            D::E => D::W,
+            D::SE => D::NW,
+            D::S => D::N,
+            D::SW => D::NE,
+            D::W => D::E,
+            D::NW => D::SE,
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.vertical().is_some() && self.horizontal().is_some()
+    }
+
+    /// The north/south component of this direction if it has one
+    pub fn vertical(&self) -> Option<Compass> {
+        use Compass8 as D;
+
+        match self {
+            D::N | D::NE | D::NW => Some(Compass::N),
+            D::S | D::SE | D::SW => Some(Compass::S),
+            D::E | D::W => None,
+        }
+    }
+
+    /// The east/west component of this direction if it has one
+    pub fn horizontal(&self) -> Option<Compass> {
+        use Compass8 as D;
+
+        match self {
+            D::E | D::NE | D::SE => Some(Compass::E),
+            D::W | D::NW | D::SW => Some(Compass::W),
+            D::N | D::S => None,
+        }
+    }
+
+    /// Get the cardinal direction equivalent to this one if it isn't diagonal
+    pub fn to_compass(&self) -> Option<Compass> {
+        match (self.vertical(), self.horizontal()) {
+            (Some(dir), None) | (None, Some(dir)) => Some(dir),
+            _ => None,
+        }
+    }
+}
+
+impl From<Compass> for Compass8 {
+    fn from(dir: Compass) -> Self {
+        match dir {
+            Compass::N => Compass8::N,
+            Compass::E => Compass8::E,
+            Compass::S => Compass8::S,
+            Compass::W => Compass8::W,
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use crate::{Compass, Compass8, DirSet, Grid, Turn};
+
+    #[test]
+    fn composes_turns() {
+        assert_eq!(Turn::Right.then(Turn::Right), Turn::Back);
+        assert_eq!(Turn::Left.then(Turn::Back), Turn::Right);
+        assert_eq!(Turn::Left.then(Turn::Right), Turn::Straight);
+        assert_eq!(Turn::Left.reverse(), Turn::Right);
+        assert_eq!(Turn::Back.reverse(), Turn::Back);
+
+        assert_eq!(Compass::N.turn_left(), Compass::W);
+        assert_eq!(Compass::W.turn_right(), Compass::N);
+        assert_eq!(Compass::E.turn(Turn::Back), Compass::W);
+
+        for from in Compass::iter() {
+            for to in Compass::iter() {
+                assert_eq!(from.turn(from.turn_to(to)), to);
+            }
+        }
+
+        assert_eq!(Compass::N.turn_to(Compass::W), Turn::Left);
+        assert_eq!(Compass::S.turn_to(Compass::N), Turn::Back);
+    }
+
+    #[test]
+    fn parses_every_notation() {
+        assert_eq!(
+            "^>v<"
+                .chars()
+                .filter_map(Compass::from_arrow)
+                .collect::<Vec<_>>(),
+            [Compass::N, Compass::E, Compass::S, Compass::W]
+        );
+        assert_eq!(
+            "NESW".chars().map(Compass::from_char).collect::<Vec<_>>(),
+            "URDL".chars().map(Compass::from_char).collect::<Vec<_>>()
+        );
+        assert_eq!(Compass::try_from('x'), Err('x'));
+        assert_eq!(Compass::try_from('v'), Ok(Compass::S));
+        assert_eq!(
+            Compass::iter().map(|dir| dir.arrow()).collect::<String>(),
+            "^>v<"
+        );
+
+        assert_eq!(Compass::W.degrees(), 270);
+        assert_eq!(Compass::from_degrees(-90), Some(Compass::W));
+        assert_eq!(Compass::from_degrees(450), Some(Compass::E));
+        assert_eq!(Compass::from_degrees(45), None);
+        assert_eq!(Compass::S.delta(), (0, 1));
+
+        assert_eq!(
+            "LRSFBX".chars().map(Turn::from_char).collect::<Vec<_>>(),
+            [
+                Some(Turn::Left),
+                Some(Turn::Right),
+                Some(Turn::Straight),
+                Some(Turn::Straight),
+                Some(Turn::Back),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn dir_sets() {
+        let mut set = DirSet::EMPTY;
+        assert!(set.is_empty());
+
+        assert!(set.insert(Compass::W));
+        assert!(set.insert(Compass::N));
+        assert!(!set.insert(Compass::W));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [Compass::N, Compass::W]);
+
+        assert!(set.remove(Compass::N));
+        assert!(!set.remove(Compass::N));
+        assert_eq!(set, DirSet::from(Compass::W));
+
+        let horizontal =
+            [Compass::E, Compass::W].into_iter().collect::<DirSet>();
+        assert_eq!(set.union(horizontal), horizontal);
+        assert_eq!(DirSet::ALL.intersection(horizontal), horizontal);
+        assert_eq!(DirSet::ALL.iter().count(), 4);
+    }
+
+    #[test]
+    fn converts_between_compasses() {
+        for dir in Compass::iter() {
+            let dir8 = Compass8::from(dir);
+            assert_eq!(dir8.to_compass(), Some(dir));
+            assert_eq!(dir8.opposite(), Compass8::from(dir.opposite()));
+            assert!(!dir8.is_diagonal());
+        }
+
+        for dir8 in Compass8::iter() {
+            assert_eq!(dir8.opposite().opposite(), dir8);
+            assert_eq!(dir8.is_diagonal(), dir8.to_compass().is_none());
+        }
+
+        assert_eq!(Compass8::SW.vertical(), Some(Compass::S));
+        assert_eq!(Compass8::SW.horizontal(), Some(Compass::W));
+    }
+
+    #[test]
+    fn diagonal_steps_stay_on_the_grid() {
+        let grid = Grid::<char>::parse_lines("abc\ndef\nghi");
+
+        assert_eq!(grid.step8_from_index(4, Compass8::NE), Some(2));
+        assert_eq!(grid.step8_from_index(4, Compass8::SW), Some(6));
+        assert_eq!(grid.step8_from_index(5, Compass8::NE), None);
+        assert_eq!(grid.step8_from_index(5, Compass8::SE), None);
+        assert_eq!(grid.step8_from_index(3, Compass8::SW), None);
+        assert_eq!(grid.step8_from_index(3, Compass8::NW), None);
+        assert_eq!(grid.step8_from_index(8, Compass8::NW), Some(4));
+
+        assert_eq!(
+            grid.neighbors8(0).collect::<Vec<_>>(),
+            [(Compass8::E, 1), (Compass8::SE, 4), (Compass8::S, 3)]
+        );
+        assert_eq!(grid.neighbors8(4).count(), 8);
+        assert_eq!(grid.escaping8(8).count(), 3);
+    }
+}
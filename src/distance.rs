@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+
+use itertools::iproduct;
+
+use crate::Grid;
+
+/// Ways of measuring the straight-line distance between two cells
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// Steps needed moving only horizontally and vertically
+    #[default]
+    Manhattan,
+    /// Steps needed when diagonal moves are also allowed
+    Chebyshev,
+    Euclidean,
+}
+
+impl Metric {
+    /// True if an offset of `dx` columns and `dy` rows is no further than `d`
+    pub fn within(&self, dx: usize, dy: usize, d: usize) -> bool {
+        match self {
+            Metric::Manhattan => dx <= d && dy <= d - dx,
+            Metric::Chebyshev => dx.max(dy) <= d,
+            Metric::Euclidean => {
+                // The square of any usize fits in a u128
+                let square = |n: usize| (n as u128).pow(2);
+                square(dx).saturating_add(square(dy)) <= square(d)
+            }
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// The number of columns and rows between two cells
+    fn offset(&self, from: usize, to: usize) -> (usize, usize) {
+        (
+            (from % self.width).abs_diff(to % self.width),
+            (from / self.width).abs_diff(to / self.width),
+        )
+    }
+
+    pub fn chebyshev_dist(&self, from: usize, to: usize) -> usize {
+        let (dx, dy) = self.offset(from, to);
+        dx.max(dy)
+    }
+
+    pub fn euclidean_dist(&self, from: usize, to: usize) -> f64 {
+        let (dx, dy) = self.offset(from, to);
+        ((dx * dx + dy * dy) as f64).sqrt()
+    }
+
+    /// Every cell no further than `d` from `i` under the given metric,
+    /// ignoring walls. Includes `i` itself, and is empty for an empty grid
+    pub fn within(
+        &self,
+        i: usize,
+        d: usize,
+        metric: Metric,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let (col, row) = (i % self.width, i / self.width);
+        let around = |at: usize, len: usize| {
+            // Nothing for an empty grid, which has no last row or column
+            len.checked_sub(1)
+                .map(|last| {
+                    at.saturating_sub(d)..=at.saturating_add(d).min(last)
+                })
+                .into_iter()
+                .flatten()
+        };
+        let (rows, cols) = (around(row, self.height), around(col, self.width));
+
+        iproduct!(rows, cols)
+            .map(|(r, c)| r * self.width + c)
+            .filter(move |j| {
+                let (dx, dy) = self.offset(i, *j);
+                metric.within(dx, dy, d)
+            })
+    }
+
+    /// Steps from the nearest of `starts` to every cell, only moving through
+    /// cells that are `passable`. Start cells are always included, and cells
+    /// that can't be reached are `None`
+    pub fn distance_map(
+        &self,
+        starts: impl IntoIterator<Item = usize>,
+        passable: impl Fn(&T) -> bool,
+    ) -> Grid<Option<usize>> {
+        let mut dist = vec![None; self.data.len()];
+        let mut queue = VecDeque::new();
+
+        for start in starts {
+            if dist[start].is_none() {
+                dist[start] = Some(0);
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(i) = queue.pop_front() {
+            let next = dist[i].map(|d| d + 1);
+
+            for (_, j) in self.neighbors(i) {
+                if dist[j].is_none() && passable(&self.data[j]) {
+                    dist[j] = next;
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        Grid::new(dist, self.width)
+    }
+}
+
+impl Grid<Option<usize>> {
+    /// Cells of a distance map that were reached in at most `d` steps
+    pub fn reached_within(&self, d: usize) -> impl Iterator<Item = usize> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |(_, dist)| dist.is_some_and(|dist| dist <= d))
+            .map(|(i, _)| i)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Grid, Metric};
+
+    const TRACK: &str = "###############\n#...#...#.....#\n#.#.#.#.#.###.#\n\
+                         #S#...#.#.#...#\n#######.#.#.###\n#######.#.#...#\n\
+                         #######.#.###.#\n###..E#...#...#\n###.#######.###\n\
+                         #...###...#...#\n#.#####.#.###.#\n#.#...#.#.#...#\n\
+                         #.#.#.#.#.#.###\n#...#...#...###\n###############";
+
+    /// Count shortcuts of up to `len` steps through walls that save at least
+    /// `min_saving` steps
+    fn count_cheats(len: usize, min_saving: usize) -> usize {
+        let grid = Grid::<char>::parse_lines(TRACK);
+        let start = grid.data.iter().position(|c| *c == 'S').unwrap();
+        let end = grid.data.iter().position(|c| *c == 'E').unwrap();
+
+        let from_start = grid.distance_map([start], |c| *c != '#');
+        let to_end = grid.distance_map([end], |c| *c != '#');
+        let best = from_start.data[end].unwrap();
+
+        from_start
+            .reached_within(best)
+            .flat_map(|i| {
+                grid.within(i, len, Metric::Manhattan).map(move |j| (i, j))
+            })
+            .filter(|(i, j)| {
+                to_end.data[*j].is_some_and(|rest| {
+                    let cheat = from_start.data[*i].unwrap()
+                        + grid.min_dist(*i, *j)
+                        + rest;
+                    cheat + min_saving <= best
+                })
+            })
+            .count()
+    }
+
+    #[test]
+    fn compares_distance_maps() {
+        assert_eq!(count_cheats(2, 20), 5);
+        assert_eq!(count_cheats(20, 76), 3);
+    }
+
+    #[test]
+    fn metrics() {
+        let grid = Grid::new(vec![0; 25], 5);
+
+        assert_eq!(grid.within(12, 1, Metric::Manhattan).count(), 5);
+        assert_eq!(grid.within(12, 1, Metric::Chebyshev).count(), 9);
+        assert_eq!(grid.within(12, 2, Metric::Euclidean).count(), 13);
+        assert_eq!(grid.within(0, 2, Metric::Manhattan).count(), 6);
+        assert_eq!(grid.chebyshev_dist(0, 24), 4);
+        assert_eq!(grid.euclidean_dist(0, 7), 5.0_f64.sqrt());
+
+        for metric in [Metric::Manhattan, Metric::Chebyshev, Metric::Euclidean]
+        {
+            assert_eq!(grid.within(12, usize::MAX, metric).count(), 25);
+            assert!(!metric.within(usize::MAX, usize::MAX, 1));
+        }
+
+        let empty = Grid::<u8>::new(vec![], 5);
+        assert_eq!(empty.within(0, 2, Metric::Manhattan).count(), 0);
+    }
+}
@@ -0,0 +1,272 @@
+use itertools::iproduct;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// The six axis-aligned directions in 3D. `N`/`S` and `E`/`W` move along the
+/// rows and columns of each layer like [`crate::Compass`] does, while `Up` and
+/// `Down` move between layers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Compass3 {
+    #[default]
+    N,
+    E,
+    S,
+    W,
+    Up,
+    Down,
+}
+
+impl Compass3 {
+    pub fn opposite(&self) -> Self {
+        use Compass3 as D;
+
+        match self {
+            D::N => D::S,
+            D::E => D::W,
+            D::S => D::N,
+            D::W => D::E,
+            D::Up => D::Down,
+            D::Down => D::Up,
+        }
+    }
+
+    /// The `(x, y, z)` offset of one step in this direction
+    pub fn delta(&self) -> (i32, i32, i32) {
+        use Compass3 as D;
+
+        match self {
+            D::N => (0, -1, 0),
+            D::E => (1, 0, 0),
+            D::S => (0, 1, 0),
+            D::W => (-1, 0, 0),
+            D::Up => (0, 0, 1),
+            D::Down => (0, 0, -1),
+        }
+    }
+}
+
+/// A dense voxel grid stored layer by layer, with each layer laid out like a
+/// [`crate::Grid`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid3<T> {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+    pub data: Vec<T>,
+}
+
+impl<T> Grid3<T> {
+    pub fn new(data: Vec<T>, width: usize, height: usize) -> Self {
+        let depth = data.len() / (width * height);
+        Self {
+            data,
+            width,
+            height,
+            depth,
+        }
+    }
+
+    fn layer_len(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn layers(&self) -> impl DoubleEndedIterator<Item = &'_ [T]> + '_ {
+        self.data.chunks(self.layer_len())
+    }
+
+    pub fn at_index(&self, i: usize) -> Option<&'_ T> {
+        self.data.get(i)
+    }
+
+    pub fn to_xyz(&self, i: usize) -> (i32, i32, i32) {
+        (
+            (i % self.width) as i32,
+            (i / self.width % self.height) as i32,
+            (i / self.layer_len()) as i32,
+        )
+    }
+
+    /// Get the flat index of the given coordinate if it lies within the grid
+    pub fn index_of(&self, x: i32, y: i32, z: i32) -> Option<usize> {
+        let in_bounds = x >= 0
+            && y >= 0
+            && z >= 0
+            && (x as usize) < self.width
+            && (y as usize) < self.height
+            && (z as usize) < self.depth;
+
+        in_bounds.then(|| {
+            x as usize + y as usize * self.width + z as usize * self.layer_len()
+        })
+    }
+
+    pub fn get(&self, x: i32, y: i32, z: i32) -> Option<&'_ T> {
+        self.index_of(x, y, z).map(|i| &self.data[i])
+    }
+
+    pub fn step_from_index(&self, i: usize, dir: Compass3) -> Option<usize> {
+        let (x, y, z) = self.to_xyz(i);
+        let (dx, dy, dz) = dir.delta();
+
+        self.index_of(x + dx, y + dy, z + dz)
+    }
+
+    pub fn neighbors(
+        &self,
+        i: usize,
+    ) -> impl Iterator<Item = (Compass3, usize)> + '_ {
+        Compass3::iter().filter_map(move |dir| {
+            self.step_from_index(i, dir).map(move |j| (dir, j))
+        })
+    }
+
+    /// Every cell that touches this one by a face, edge or corner
+    pub fn neighbors26(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y, z) = self.to_xyz(i);
+
+        iproduct!(-1..=1, -1..=1, -1..=1)
+            .filter(|d| *d != (0, 0, 0))
+            .filter_map(move |(dx, dy, dz)| {
+                self.index_of(x + dx, y + dy, z + dz)
+            })
+    }
+
+    pub fn is_border(&self, i: usize) -> bool {
+        let (x, y, z) = self.to_xyz(i);
+
+        x == 0
+            || y == 0
+            || z == 0
+            || x as usize == self.width - 1
+            || y as usize == self.height - 1
+            || z as usize == self.depth - 1
+    }
+
+    /// Mark every non-solid cell that can be reached from outside the grid by
+    /// moving between non-solid cells through their faces
+    pub fn exterior(&self, solid: impl Fn(&T) -> bool) -> Grid3<bool> {
+        let mut outside = vec![false; self.data.len()];
+        let mut stack = (0..self.data.len())
+            .filter(|i| self.is_border(*i) && !solid(&self.data[*i]))
+            .collect::<Vec<_>>();
+
+        for i in &stack {
+            outside[*i] = true;
+        }
+
+        while let Some(i) = stack.pop() {
+            for (_, j) in self.neighbors(i) {
+                if !outside[j] && !solid(&self.data[j]) {
+                    outside[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+
+        Grid3::new(outside, self.width, self.height)
+    }
+
+    /// The number of faces of solid cells that aren't touching another solid
+    /// cell, including faces of enclosed air pockets
+    pub fn surface_area(&self, solid: impl Fn(&T) -> bool) -> usize {
+        self.count_faces(&solid, |j| !solid(&self.data[j]))
+    }
+
+    /// The number of faces of solid cells that can be reached from outside
+    /// the grid
+    pub fn exterior_surface_area(&self, solid: impl Fn(&T) -> bool) -> usize {
+        let outside = self.exterior(&solid);
+        self.count_faces(&solid, |j| outside.data[j])
+    }
+
+    /// Count faces of solid cells whose neighbor is either off the grid or
+    /// matches `exposed`
+    fn count_faces(
+        &self,
+        solid: &impl Fn(&T) -> bool,
+        exposed: impl Fn(usize) -> bool,
+    ) -> usize {
+        (0..self.data.len())
+            .filter(|i| solid(&self.data[*i]))
+            .map(|i| {
+                Compass3::iter()
+                    .filter(|dir| {
+                        self.step_from_index(i, *dir).is_none_or(&exposed)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl Grid3<bool> {
+    /// Build a grid just big enough to hold every point, leaving a one cell
+    /// gap around the outside so the exterior is connected. Returns the grid
+    /// along with the coordinate that maps to index 0
+    pub fn from_points(
+        points: &[(i32, i32, i32)],
+    ) -> (Grid3<bool>, (i32, i32, i32)) {
+        let min = |f: fn(&(i32, i32, i32)) -> i32| {
+            points.iter().map(f).min().unwrap_or(0) - 1
+        };
+        let max = |f: fn(&(i32, i32, i32)) -> i32| {
+            points.iter().map(f).max().unwrap_or(0) + 1
+        };
+
+        let origin = (min(|p| p.0), min(|p| p.1), min(|p| p.2));
+        let width = (max(|p| p.0) - origin.0 + 1) as usize;
+        let height = (max(|p| p.1) - origin.1 + 1) as usize;
+        let depth = (max(|p| p.2) - origin.2 + 1) as usize;
+
+        let mut grid =
+            Grid3::new(vec![false; width * height * depth], width, height);
+
+        for (x, y, z) in points {
+            let i = grid
+                .index_of(x - origin.0, y - origin.1, z - origin.2)
+                .unwrap();
+            grid.data[i] = true;
+        }
+
+        (grid, origin)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::Grid3;
+
+    #[test]
+    fn surface_areas() {
+        let points = [
+            (2, 2, 2),
+            (1, 2, 2),
+            (3, 2, 2),
+            (2, 1, 2),
+            (2, 3, 2),
+            (2, 2, 1),
+            (2, 2, 3),
+            (2, 2, 4),
+            (2, 2, 6),
+            (1, 2, 5),
+            (3, 2, 5),
+            (2, 1, 5),
+            (2, 3, 5),
+        ];
+
+        let (grid, _) = Grid3::from_points(&points);
+
+        assert_eq!(grid.surface_area(|t| *t), 64);
+        assert_eq!(grid.exterior_surface_area(|t| *t), 58);
+    }
+
+    #[test]
+    fn neighbor_counts() {
+        let grid = Grid3::new(vec![0; 27], 3, 3);
+
+        assert_eq!(grid.depth, 3);
+        assert_eq!(grid.neighbors(13).count(), 6);
+        assert_eq!(grid.neighbors26(13).count(), 26);
+        assert_eq!(grid.neighbors(0).count(), 3);
+        assert_eq!(grid.neighbors26(0).count(), 7);
+    }
+}
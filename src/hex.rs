@@ -0,0 +1,323 @@
+use std::{
+    collections::HashMap,
+    ops::{Add, AddAssign, Sub},
+    str::FromStr,
+};
+
+use strum::{EnumIter, IntoEnumIterator};
+
+/// The six directions on a flat-topped hex grid
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum HexDir {
+    #[default]
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl HexDir {
+    pub fn opposite(&self) -> Self {
+        use HexDir as D;
+
+        match self {
+            D::N => D::S,
+            D::NE => D::SW,
+            D::SE => D::NW,
+            D::S => D::N,
+            D::SW => D::NE,
+            D::NW => D::SE,
+        }
+    }
+
+    /// Parse a run of directions with no separators like `"nesenw"`. Runs
+    /// can also be separated by commas and whitespace, but a separator can't
+    /// split a direction in two
+    pub fn parse_path(input: &str) -> Option<Vec<HexDir>> {
+        let mut result = vec![];
+
+        for run in input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|run| !run.is_empty())
+        {
+            let mut chars = run.chars().peekable();
+
+            while let Some(c) = chars.next() {
+                let vertical = match c {
+                    'n' => HexDir::N,
+                    's' => HexDir::S,
+                    _ => return None,
+                };
+
+                let dir = match (vertical, chars.peek()) {
+                    (HexDir::N, Some('e')) => HexDir::NE,
+                    (HexDir::N, Some('w')) => HexDir::NW,
+                    (HexDir::S, Some('e')) => HexDir::SE,
+                    (HexDir::S, Some('w')) => HexDir::SW,
+                    _ => {
+                        result.push(vertical);
+                        continue;
+                    }
+                };
+
+                chars.next();
+                result.push(dir);
+            }
+        }
+
+        Some(result)
+    }
+}
+
+/// An error which can be returned when parsing a [`HexDir`].
+#[derive(Debug)]
+pub struct HexDirFromStrError;
+
+impl std::error::Error for HexDirFromStrError {}
+
+impl std::fmt::Display for HexDirFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of n, ne, se, s, sw or nw")
+    }
+}
+
+impl FromStr for HexDir {
+    type Err = HexDirFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(HexDir::N),
+            "ne" => Ok(HexDir::NE),
+            "se" => Ok(HexDir::SE),
+            "s" => Ok(HexDir::S),
+            "sw" => Ok(HexDir::SW),
+            "nw" => Ok(HexDir::NW),
+            _ => Err(HexDirFromStrError),
+        }
+    }
+}
+
+/// An axial hex coordinate. The implied third cube coordinate is
+/// [`Hex::s`], so `q + r + s == 0` always holds
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub const fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        debug_assert_eq!(q + r + s, 0);
+        Self::new(q, r)
+    }
+
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn unit(dir: HexDir) -> Self {
+        use HexDir as D;
+
+        match dir {
+            D::N => Self::new(0, -1),
+            D::NE => Self::new(1, -1),
+            D::SE => Self::new(1, 0),
+            D::S => Self::new(0, 1),
+            D::SW => Self::new(-1, 1),
+            D::NW => Self::new(-1, 0),
+        }
+    }
+
+    pub fn step(self, dir: HexDir) -> Self {
+        self + Self::unit(dir)
+    }
+
+    /// The number of steps needed to get from one hex to the other
+    pub fn distance(&self, other: &Self) -> u32 {
+        let d = *self - *other;
+        (d.q.unsigned_abs() + d.r.unsigned_abs() + d.s().unsigned_abs()) / 2
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = (HexDir, Hex)> {
+        HexDir::iter().map(move |dir| (dir, self.step(dir)))
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Add<HexDir> for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: HexDir) -> Self::Output {
+        self.step(rhs)
+    }
+}
+
+impl AddAssign<HexDir> for Hex {
+    fn add_assign(&mut self, rhs: HexDir) {
+        *self = self.step(rhs);
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+/// A map of hex tiles keyed by coordinate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexMap<T> {
+    pub tiles: HashMap<Hex, T>,
+}
+
+impl<T> Default for HexMap<T> {
+    fn default() -> Self {
+        Self {
+            tiles: HashMap::new(),
+        }
+    }
+}
+
+impl<T> HexMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<&'_ T> {
+        self.tiles.get(&hex)
+    }
+
+    pub fn get_mut(&mut self, hex: Hex) -> Option<&'_ mut T> {
+        self.tiles.get_mut(&hex)
+    }
+
+    pub fn insert(&mut self, hex: Hex, value: T) -> Option<T> {
+        self.tiles.insert(hex, value)
+    }
+
+    pub fn remove(&mut self, hex: Hex) -> Option<T> {
+        self.tiles.remove(&hex)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &'_ T)> + '_ {
+        self.tiles.iter().map(|(h, t)| (*h, t))
+    }
+
+    /// All six neighboring coordinates whether or not they hold a tile
+    pub fn neighbors(
+        &self,
+        hex: Hex,
+    ) -> impl Iterator<Item = (HexDir, Hex)> + '_ {
+        hex.neighbors()
+    }
+
+    /// Neighboring coordinates that hold a tile
+    pub fn occupied_neighbors(
+        &self,
+        hex: Hex,
+    ) -> impl Iterator<Item = (HexDir, Hex, &'_ T)> + '_ {
+        hex.neighbors()
+            .filter_map(|(dir, h)| self.get(h).map(|t| (dir, h, t)))
+    }
+}
+
+impl<T> FromIterator<(Hex, T)> for HexMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Hex, T)>>(iter: I) -> Self {
+        Self {
+            tiles: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use crate::{Hex, HexDir, HexMap};
+
+    fn walk(path: &str) -> Hex {
+        HexDir::parse_path(path)
+            .unwrap()
+            .into_iter()
+            .fold(Hex::ORIGIN, |hex, dir| hex + dir)
+    }
+
+    #[test]
+    fn parses_paths() {
+        use HexDir as D;
+
+        assert_eq!(
+            HexDir::parse_path("nesenw"),
+            Some(vec![D::NE, D::SE, D::NW])
+        );
+        assert_eq!(
+            HexDir::parse_path("n,ne, s,sw\n"),
+            Some(vec![D::N, D::NE, D::S, D::SW])
+        );
+        assert_eq!(HexDir::parse_path(""), Some(vec![]));
+        assert_eq!(HexDir::parse_path("nex"), None);
+        assert_eq!(HexDir::parse_path("n,e"), None);
+        assert_eq!(HexDir::parse_path("s w"), None);
+        assert_eq!(HexDir::parse_path("e"), None);
+        assert_eq!("sw".parse::<HexDir>().ok(), Some(D::SW));
+        assert!("ew".parse::<HexDir>().is_err());
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(walk("ne,ne,ne").distance(&Hex::ORIGIN), 3);
+        assert_eq!(walk("ne,ne,sw,sw"), Hex::ORIGIN);
+        assert_eq!(walk("ne,ne,s,s").distance(&Hex::ORIGIN), 2);
+        assert_eq!(walk("se,sw,se,sw,sw").distance(&Hex::ORIGIN), 3);
+        assert_eq!(Hex::new(2, -3).distance(&Hex::new(-1, 1)), 4);
+
+        for dir in HexDir::iter() {
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_eq!(Hex::ORIGIN + dir + dir.opposite(), Hex::ORIGIN);
+            assert_eq!((Hex::ORIGIN + dir).distance(&Hex::ORIGIN), 1);
+        }
+    }
+
+    #[test]
+    fn occupied_neighbors() {
+        let map = [Hex::ORIGIN, walk("n"), walk("sesw"), walk("nn")]
+            .into_iter()
+            .map(|hex| (hex, ()))
+            .collect::<HexMap<_>>();
+
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.neighbors(Hex::ORIGIN).count(), 6);
+        assert_eq!(
+            map.occupied_neighbors(Hex::ORIGIN)
+                .map(|(dir, _, _)| dir)
+                .collect::<Vec<_>>(),
+            [HexDir::N, HexDir::S]
+        );
+        assert_eq!(map.occupied_neighbors(walk("n")).count(), 2);
+    }
+}
@@ -0,0 +1,380 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// Directory under `data/` that frame recordings are written to
+fn frames_dir() -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    cwd.join("data").join("frames")
+}
+
+/// Convert a pixel dimension to the narrower integer an image format stores
+/// it as, failing instead of truncating if it doesn't fit
+fn dimension<N: TryFrom<usize>>(pixels: usize) -> io::Result<N> {
+    N::try_from(pixels).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("image dimension {pixels} is too large for the format"),
+        )
+    })
+}
+
+impl<T> Grid<T> {
+    /// Rasterize the grid to packed RGB bytes with every cell drawn as a
+    /// `scale` x `scale` square. Returns the pixel width, height and data
+    pub fn to_rgb(
+        &self,
+        scale: usize,
+        color: impl Fn(&T) -> Rgb,
+    ) -> (usize, usize, Vec<u8>) {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+
+        for row in self.rows() {
+            let line = row
+                .iter()
+                .flat_map(|t| {
+                    let rgb = color(t);
+                    (0..scale).flat_map(move |_| rgb)
+                })
+                .collect::<Vec<_>>();
+
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+
+        (width, height, pixels)
+    }
+
+    /// Write the grid as a binary PPM image
+    pub fn write_ppm(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        color: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        let (width, height, pixels) = self.to_rgb(scale, color);
+        let mut out = BufWriter::new(File::create(path)?);
+
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        out.write_all(&pixels)?;
+        out.flush()
+    }
+
+    /// Write the grid as a PNG image
+    pub fn write_png(
+        &self,
+        path: impl AsRef<Path>,
+        scale: usize,
+        color: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        let (width, height, pixels) = self.to_rgb(scale, color);
+        let (width, height) = (dimension(width)?, dimension(height)?);
+        let out = BufWriter::new(File::create(path)?);
+
+        let mut encoder = png::Encoder::new(out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(io::Error::other)
+    }
+}
+
+enum FrameTarget {
+    Series(PathBuf),
+    Gif {
+        path: PathBuf,
+        delay: u16,
+        size: Option<(u16, u16)>,
+        encoder: Option<gif::Encoder<BufWriter<File>>>,
+    },
+}
+
+/// Records a sequence of grids (e.g. each step of a simulation) to
+/// `data/frames/`, either as numbered PNG files or as one animated GIF
+pub struct FrameRecorder {
+    target: FrameTarget,
+    scale: usize,
+    frames: usize,
+}
+
+impl FrameRecorder {
+    /// Write each frame to `data/frames/<name>/<frame>.png`
+    pub fn series(name: &str, scale: usize) -> io::Result<Self> {
+        Self::series_in(frames_dir().join(name), scale)
+    }
+
+    /// Write each frame to `<dir>/<frame>.png`
+    pub fn series_in(
+        dir: impl Into<PathBuf>,
+        scale: usize,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            target: FrameTarget::Series(dir),
+            scale,
+            frames: 0,
+        })
+    }
+
+    /// Write every frame into `data/frames/<name>.gif`. See
+    /// [`FrameRecorder::gif_at`]
+    pub fn gif(name: &str, scale: usize, delay_ms: u16) -> io::Result<Self> {
+        Self::gif_at(frames_dir().join(format!("{name}.gif")), scale, delay_ms)
+    }
+
+    /// Write every frame into the GIF at `path`, showing each one for
+    /// `delay_ms` milliseconds. GIFs store delays in hundredths of a second,
+    /// so this is rounded up to the next 10ms. All frames must be the same
+    /// size
+    pub fn gif_at(
+        path: impl Into<PathBuf>,
+        scale: usize,
+        delay_ms: u16,
+    ) -> io::Result<Self> {
+        let path = path.into();
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        Ok(Self {
+            target: FrameTarget::Gif {
+                path,
+                delay: delay_ms.div_ceil(10),
+                size: None,
+                encoder: None,
+            },
+            scale,
+            frames: 0,
+        })
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn record<T>(
+        &mut self,
+        grid: &Grid<T>,
+        color: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        match &mut self.target {
+            FrameTarget::Series(dir) => {
+                let path = dir.join(format!("{:05}.png", self.frames));
+                grid.write_png(path, self.scale, color)?;
+            }
+            FrameTarget::Gif {
+                path,
+                delay,
+                size,
+                encoder,
+            } => {
+                let (width, height, pixels) = grid.to_rgb(self.scale, color);
+                let (width, height) = (dimension(width)?, dimension(height)?);
+
+                let (first_width, first_height) =
+                    *size.get_or_insert((width, height));
+
+                if (first_width, first_height) != (width, height) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "frame {} is {width}x{height} but the first frame \
+                             was {first_width}x{first_height}",
+                            self.frames
+                        ),
+                    ));
+                }
+
+                let encoder = match encoder {
+                    Some(encoder) => encoder,
+                    None => {
+                        let out = BufWriter::new(File::create(path)?);
+                        let mut created =
+                            gif::Encoder::new(out, width, height, &[])
+                                .map_err(io::Error::other)?;
+                        created
+                            .set_repeat(gif::Repeat::Infinite)
+                            .map_err(io::Error::other)?;
+                        encoder.insert(created)
+                    }
+                };
+
+                let mut frame = palette_frame(width, height, &pixels)
+                    .unwrap_or_else(|| {
+                        gif::Frame::from_rgb_speed(width, height, &pixels, 10)
+                    });
+                frame.delay = *delay;
+
+                encoder.write_frame(&frame).map_err(io::Error::other)?;
+            }
+        }
+
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Finish writing the recording, reporting any error that would
+    /// otherwise be lost when the recorder is dropped
+    pub fn finish(self) -> io::Result<()> {
+        match self.target {
+            FrameTarget::Gif {
+                encoder: Some(encoder),
+                ..
+            } => encoder.into_inner()?.flush(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Build a frame with an exact palette if the image has at most 256 colors,
+/// which is almost always the case for puzzle grids
+fn palette_frame(
+    width: u16,
+    height: u16,
+    pixels: &[u8],
+) -> Option<gif::Frame<'static>> {
+    let mut palette = HashMap::<Rgb, u8>::new();
+    let mut indices = Vec::with_capacity(pixels.len() / 3);
+
+    for rgb in pixels.chunks_exact(3) {
+        let rgb = [rgb[0], rgb[1], rgb[2]];
+        let next = palette.len();
+
+        let index = match palette.get(&rgb) {
+            Some(index) => *index,
+            None => {
+                let index = u8::try_from(next).ok()?;
+                palette.insert(rgb, index);
+                index
+            }
+        };
+
+        indices.push(index);
+    }
+
+    let mut colors = vec![0; palette.len() * 3];
+
+    for (rgb, index) in palette {
+        let start = index as usize * 3;
+        colors[start..start + 3].copy_from_slice(&rgb);
+    }
+
+    Some(gif::Frame::from_palette_pixels(
+        width, height, indices, colors, None,
+    ))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, io, path::PathBuf};
+
+    use crate::{FrameRecorder, Grid, Rgb};
+
+    use super::dimension;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("advent_of_code_{name}_{}", std::process::id()))
+    }
+
+    fn color(c: &char) -> Rgb {
+        if *c == '#' {
+            [255, 0, 0]
+        } else {
+            [0; 3]
+        }
+    }
+
+    #[test]
+    fn writes_images() {
+        let dir = temp_dir("images");
+        fs::create_dir_all(&dir).unwrap();
+
+        let grid = Grid::<char>::parse_lines("#..\n.#.");
+
+        grid.write_ppm(dir.join("grid.ppm"), 2, color).unwrap();
+        let ppm = fs::read(dir.join("grid.ppm")).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        assert_eq!(ppm[header.len()..header.len() + 3], [255, 0, 0]);
+
+        grid.write_png(dir.join("grid.png"), 3, color).unwrap();
+        let png = fs::read(dir.join("grid.png")).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(png[16..20], 9u32.to_be_bytes());
+        assert_eq!(png[20..24], 6u32.to_be_bytes());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn records_series() {
+        let dir = temp_dir("series");
+        let mut recorder = FrameRecorder::series_in(&dir, 2).unwrap();
+
+        recorder.record(&Grid::parse_lines("#."), color).unwrap();
+        recorder
+            .record(&Grid::parse_lines(".#\n#."), color)
+            .unwrap();
+        assert_eq!(recorder.frames(), 2);
+        recorder.finish().unwrap();
+
+        let png = fs::read(dir.join("00001.png")).unwrap();
+        assert_eq!(png[16..24], [0, 0, 0, 4, 0, 0, 0, 4]);
+        assert!(dir.join("00000.png").exists());
+        assert!(!dir.join("00002.png").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn records_gifs() {
+        let dir = temp_dir("gif");
+        let path = dir.join("frames.gif");
+        let mut recorder = FrameRecorder::gif_at(&path, 1, 5).unwrap();
+
+        recorder
+            .record(&Grid::parse_lines("#.\n.#"), color)
+            .unwrap();
+        recorder
+            .record(&Grid::parse_lines(".#\n#."), color)
+            .unwrap();
+
+        let mismatch = recorder.record(&Grid::parse_lines("#.."), color);
+        assert_eq!(
+            mismatch.map_err(|e| e.kind()),
+            Err(io::ErrorKind::InvalidInput)
+        );
+        assert_eq!(recorder.frames(), 2);
+        recorder.finish().unwrap();
+
+        let gif = fs::read(&path).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x02\x00\x02\x00"));
+        assert_eq!(gif.last(), Some(&0x3b));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_oversized_dimensions() {
+        assert_eq!(dimension::<u16>(65_535).unwrap(), 65_535);
+        assert!(dimension::<u16>(65_536).is_err());
+    }
+}
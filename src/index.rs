@@ -0,0 +1,208 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Grid, Point};
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.data[i]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        &mut self.data[i]
+    }
+}
+
+/// Index by `(col, row)`
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (col, row): (usize, usize)) -> &Self::Output {
+        assert!(col < self.width, "column {col} out of bounds");
+        &self.data[col + row * self.width]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut Self::Output {
+        assert!(col < self.width, "column {col} out of bounds");
+        &mut self.data[col + row * self.width]
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        let i = self.index_of(p).expect("point out of bounds");
+        &self.data[i]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+        let i = self.index_of(p).expect("point out of bounds");
+        &mut self.data[i]
+    }
+}
+
+impl<T> Grid<T> {
+    /// Get the cell at the given signed coordinates if it's in bounds
+    pub fn get(&self, col: i32, row: i32) -> Option<&'_ T> {
+        self.index_of(Point::new(col, row)).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, col: i32, row: i32) -> Option<&'_ mut T> {
+        self.index_of(Point::new(col, row))
+            .map(|i| &mut self.data[i])
+    }
+
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &'_ T> {
+        // Empty for a column that doesn't exist
+        let len = if col < self.width { self.height } else { 0 };
+
+        self.data.iter().skip(col).step_by(self.width).take(len)
+    }
+
+    pub fn cols(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &'_ T>>
+    {
+        (0..self.width).map(|col| self.col(col))
+    }
+
+    pub fn iter_with_pos(&self) -> impl Iterator<Item = (Point, &'_ T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (self.point_of(i), t))
+    }
+
+    /// Index of the first cell matching the predicate
+    pub fn find(&self, pred: impl Fn(&T) -> bool) -> Option<usize> {
+        self.data.iter().position(pred)
+    }
+
+    pub fn count(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.data.iter().filter(|t| pred(t)).count()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    pub fn positions_of<'a>(
+        &'a self,
+        value: &'a T,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |(_, t)| *t == value)
+            .map(|(i, _)| i)
+    }
+}
+
+/// Build a grid from rows, all of which must be the same length. Like
+/// [`Grid::new`] this panics if there are no rows or they're empty
+impl<T> FromIterator<Vec<T>> for Grid<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter: I) -> Self {
+        let mut width = None;
+        let mut data = vec![];
+
+        for row in iter {
+            let expected = *width.get_or_insert(row.len());
+            assert_eq!(row.len(), expected, "rows must all be the same width");
+            data.extend(row);
+        }
+
+        match width {
+            Some(width) if width > 0 => Grid::new(data, width),
+            _ => panic!("a grid needs at least one non-empty row"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Grid, Point};
+
+    #[test]
+    fn indexes_by_position() {
+        let mut grid = Grid::<char>::parse_lines("abc\ndef");
+
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid[Point::new(1, 0)], 'b');
+        assert_eq!(grid.get(0, 1), Some(&'d'));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+
+        grid[(0, 0)] = 'x';
+        grid[Point::new(2, 1)] = 'y';
+        *grid.get_mut(1, 1).unwrap() = 'z';
+        assert_eq!(grid, Grid::parse_lines("xbc\ndzy"));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of bounds")]
+    fn rejects_columns_past_the_edge() {
+        let _ = Grid::<char>::parse_lines("abc\ndef")[(3, 0)];
+    }
+
+    #[test]
+    fn iterates_columns() {
+        let grid = Grid::<char>::parse_lines("abc\ndef");
+
+        assert_eq!(grid.col(1).collect::<String>(), "be");
+        assert_eq!(grid.col(2).rev().collect::<String>(), "fc");
+        assert_eq!(grid.col(3).count(), 0);
+        assert_eq!(
+            grid.cols()
+                .rev()
+                .map(|col| col.collect())
+                .collect::<Vec<String>>(),
+            ["cf", "be", "ad"]
+        );
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::<char>::parse_lines("#.#\n.##");
+
+        assert_eq!(grid.positions_of(&'#').collect::<Vec<_>>(), [0, 2, 4, 5]);
+        assert_eq!(grid.find(|c| *c == '.'), Some(1));
+        assert_eq!(grid.count(|c| *c == '#'), 4);
+    }
+
+    #[test]
+    fn collects_rows() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]]
+            .into_iter()
+            .collect::<Grid<_>>();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid[(0, 1)], 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "a grid needs at least one non-empty row")]
+    fn rejects_no_rows() {
+        let _ = std::iter::empty::<Vec<u8>>().collect::<Grid<_>>();
+    }
+
+    #[test]
+    #[should_panic(expected = "a grid needs at least one non-empty row")]
+    fn rejects_empty_rows() {
+        let _ = vec![vec![], vec![]].into_iter().collect::<Grid<u8>>();
+    }
+
+    #[test]
+    #[should_panic(expected = "rows must all be the same width")]
+    fn rejects_ragged_rows() {
+        let _ = vec![vec![1, 2], vec![3]].into_iter().collect::<Grid<_>>();
+    }
+}
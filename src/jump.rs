@@ -0,0 +1,226 @@
+use strum::IntoEnumIterator;
+
+use crate::{BitGrid, Compass, Compass8, DirSet, Grid, Turn, WalkOutcome};
+
+/// For every cell and direction, the nearest obstacle strictly beyond that
+/// cell, so movement that only stops at obstacles can skip straight to them
+/// instead of stepping one cell at a time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpTable {
+    blocked: BitGrid,
+    next: Vec<[Option<usize>; 4]>,
+}
+
+impl JumpTable {
+    pub fn new(blocked: BitGrid) -> Self {
+        let len = blocked.len();
+        let mut next = vec![[None; 4]; len];
+
+        for dir in Compass::iter() {
+            let d = dir.quarter_turns() as usize;
+
+            // Visit cells so the one ahead of each is always done first
+            let order: Box<dyn Iterator<Item = usize>> = match dir {
+                Compass::N | Compass::W => Box::new(0..len),
+                Compass::S | Compass::E => Box::new((0..len).rev()),
+            };
+
+            for i in order {
+                next[i][d] = blocked.step_from_index(i, dir).and_then(|j| {
+                    if blocked.get(j) {
+                        Some(j)
+                    } else {
+                        next[j][d]
+                    }
+                });
+            }
+        }
+
+        Self { blocked, next }
+    }
+
+    pub fn is_blocked(&self, i: usize) -> bool {
+        self.blocked.get(i)
+    }
+
+    /// The first obstacle met moving from `i` in `dir`, or `None` if the
+    /// border comes first
+    pub fn next_obstacle(&self, i: usize, dir: Compass) -> Option<usize> {
+        self.next[i][dir.quarter_turns() as usize]
+    }
+
+    /// The cell you stop on moving from `i` in `dir` until something is in
+    /// the way, or `None` if nothing is and you leave the grid
+    pub fn jump(&self, i: usize, dir: Compass) -> Option<usize> {
+        self.next_obstacle(i, dir)
+            .map(|o| self.blocked.step_from_index(o, dir.opposite()).unwrap())
+    }
+
+    /// Point every cell whose ray in some direction reaches `o` (through the
+    /// cell just behind it and back to the previous obstacle) at `target`
+    fn retarget(
+        &mut self,
+        o: usize,
+        target: impl Fn(&Self, Compass) -> Option<usize>,
+    ) {
+        for dir in Compass::iter() {
+            let d = dir.quarter_turns() as usize;
+            let target = target(self, dir);
+            let mut at = self.blocked.step_from_index(o, dir.opposite());
+
+            while let Some(c) = at {
+                self.next[c][d] = target;
+
+                if self.blocked.get(c) {
+                    break;
+                }
+
+                at = self.blocked.step_from_index(c, dir.opposite());
+            }
+        }
+    }
+
+    /// Add an obstacle, only updating the rows and columns that can see it
+    pub fn insert(&mut self, o: usize) {
+        if self.blocked.insert(o) {
+            self.retarget(o, |_, _| Some(o));
+        }
+    }
+
+    /// Remove an obstacle, handing everything that could see it over to
+    /// whatever was behind it
+    pub fn remove(&mut self, o: usize) {
+        if self.blocked.remove(o) {
+            self.retarget(o, |table, dir| table.next_obstacle(o, dir));
+        }
+    }
+
+    /// Walk from `start`, turning at every obstacle, until leaving the grid
+    /// or repeating a stop with the same heading
+    pub fn walk(
+        &self,
+        start: usize,
+        heading: Compass,
+        turn: Turn,
+    ) -> WalkOutcome {
+        let mut seen = vec![DirSet::EMPTY; self.next.len()];
+        let (mut pos, mut heading) = (start, heading);
+
+        loop {
+            let Some(stop) = self.jump(pos, heading) else {
+                return WalkOutcome::Exit;
+            };
+
+            if !seen[stop].insert(heading) {
+                return WalkOutcome::Loop;
+            }
+
+            pos = stop;
+            heading = heading.turn(turn);
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn jump_table(&self, blocked: impl Fn(&T) -> bool) -> JumpTable {
+        JumpTable::new(self.to_bit_grid(blocked))
+    }
+
+    /// The cells beyond `i` in a straight line in the given direction, up to
+    /// the border
+    pub fn ray(
+        &self,
+        i: usize,
+        dir: Compass8,
+    ) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.step8_from_index(i, dir), move |j| {
+            self.step8_from_index(*j, dir)
+        })
+    }
+
+    /// The first cell beyond `i` in the given direction that matches `stop`,
+    /// i.e. the first thing that can be seen looking that way
+    pub fn cast_ray(
+        &self,
+        i: usize,
+        dir: Compass8,
+        stop: impl Fn(&T) -> bool,
+    ) -> Option<usize> {
+        self.ray(i, dir).find(|j| stop(&self.data[*j]))
+    }
+
+    /// For each of the eight directions, the first cell matching `stop`
+    /// visible from `i`
+    pub fn line_of_sight<'a>(
+        &'a self,
+        i: usize,
+        stop: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Compass8, usize)> + 'a {
+        Compass8::iter().filter_map(move |dir| {
+            self.cast_ray(i, dir, &stop).map(|j| (dir, j))
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{walker::LAB, Compass, Compass8, Grid, Turn, WalkOutcome};
+
+    #[test]
+    fn incremental_updates_match_rebuild() {
+        let grid = Grid::<char>::parse_lines(LAB);
+        let mut table = grid.jump_table(|c| *c == '#');
+        let fresh = table.clone();
+
+        assert_eq!(table.jump(64, Compass::N), Some(14));
+        assert_eq!(table.next_obstacle(64, Compass::W), Some(61));
+        assert_eq!(table.jump(64, Compass::S), None);
+
+        for o in [44, 0, 99, 67] {
+            let mut with_block = grid.clone();
+            with_block.data[o] = '#';
+
+            table.insert(o);
+            assert_eq!(table, with_block.jump_table(|c| *c == '#'));
+            table.remove(o);
+            assert_eq!(table, fresh);
+        }
+    }
+
+    #[test]
+    fn jumping_walk_finds_loops() {
+        let grid = Grid::<char>::parse_lines(LAB);
+        let start = grid.data.iter().position(|c| *c == '^').unwrap();
+        let mut table = grid.jump_table(|c| *c == '#');
+
+        assert_eq!(
+            table.walk(start, Compass::N, Turn::Right),
+            WalkOutcome::Exit
+        );
+
+        let candidates = (0..grid.data.len())
+            .filter(|o| *o != start && !table.is_blocked(*o))
+            .collect::<Vec<_>>();
+        let loops = candidates
+            .into_iter()
+            .filter(|o| {
+                table.insert(*o);
+                let outcome = table.walk(start, Compass::N, Turn::Right);
+                table.remove(*o);
+                outcome == WalkOutcome::Loop
+            })
+            .count();
+
+        assert_eq!(loops, 6);
+    }
+
+    #[test]
+    fn casts_rays() {
+        let grid = Grid::<char>::parse_lines(LAB);
+
+        assert_eq!(grid.ray(64, Compass8::N).count(), 6);
+        assert_eq!(grid.cast_ray(64, Compass8::N, |c| *c == '#'), Some(4));
+        assert_eq!(grid.cast_ray(64, Compass8::S, |c| *c == '#'), None);
+        assert_eq!(grid.line_of_sight(64, |c| *c == '#').count(), 3);
+    }
+}
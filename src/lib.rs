@@ -1,11 +1,54 @@
+mod automaton;
+mod bitgrid;
+mod compass;
 mod day;
+mod distance;
+mod grid3;
+mod hex;
+mod image;
+mod index;
+mod jump;
+mod padded;
+mod parse;
+mod pathfinding;
+mod pattern;
+mod point;
+mod ranges;
+mod regions;
+mod render;
+mod sections;
+mod sparse;
 pub mod template;
+mod transform;
+mod traverse;
+mod walker;
+mod wrapping;
 
 use std::ops::Range;
 
+pub use automaton::*;
+pub use bitgrid::*;
+pub use compass::*;
 pub use day::*;
+pub use distance::*;
+pub use grid3::*;
+pub use hex::*;
+pub use image::*;
+pub use jump::*;
+pub use padded::*;
+pub use parse::*;
+pub use pathfinding::*;
+pub use pattern::*;
+pub use point::*;
+pub use ranges::*;
+pub use regions::*;
+pub use render::*;
+pub use sections::*;
+pub use sparse::*;
+pub use transform::*;
+pub use walker::*;
+pub use wrapping::*;
 
-use itertools::Itertools;
 use nom::{
     bytes::complete::{tag, take_until},
     character::complete::multispace0,
@@ -13,7 +56,7 @@ use nom::{
     sequence::{delimited, preceded},
     Parser,
 };
-use strum::{EnumIter, IntoEnumIterator};
+use strum::IntoEnumIterator;
 use tinyvec::TinyVec;
 
 pub type TV4<K> = TinyVec<[K; 4]>;
@@ -51,38 +94,6 @@ where
     (max_start < min_end).then(|| max_start..min_end)
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
-pub enum Compass {
-    #[default]
-    N,
-    E,
-    S,
-    W,
-}
-
-impl Compass {
-    pub fn from_relative(relative: char) -> Option<Self> {
-        match relative {
-            'U' => Some(Compass::N),
-            'D' => Some(Compass::S),
-            'L' => Some(Compass::W),
-            'R' => Some(Compass::E),
-            _ => None,
-        }
-    }
-
-    pub fn opposite(&self) -> Self {
-        use Compass as D;
-
-        match self {
-            D::E => D::W,
-            D::N => D::S,
-            D::W => D::E,
-            D::S => D::N,
-        }
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Grid<T> {
     pub width: usize,
@@ -94,19 +105,11 @@ impl<T> Grid<T>
 where
     T: From<char>,
 {
+    /// Parse a grid from characters, panicking with the offending line if the
+    /// input isn't rectangular. See [`Grid::try_parse`] for the fallible
+    /// version
     pub fn parse_lines(input: &str) -> Self {
-        let width = input.find('\n').unwrap_or(input.len());
-
-        // Double check all the lines match the expected width
-        debug_assert!(!input.lines().any(|line| line.len() != width));
-
-        let data = input
-            .chars()
-            .filter(|c| *c != '\n')
-            .map(T::from)
-            .collect_vec();
-
-        Self::new(data, width)
+        Self::try_parse(input).unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -116,12 +119,9 @@ where
     T: Copy,
 {
     pub fn print(&self) {
-        self.rows().for_each(|row| {
-            println!(
-                "{}",
-                row.iter().map(|t| char::from(*t)).collect::<String>()
-            )
-        });
+        if !self.data.is_empty() {
+            println!("{self}");
+        }
     }
 }
 
@@ -150,7 +150,9 @@ impl<T> Grid<T> {
         F: FnMut(&'_ mut [T], &'_ mut [T]),
     {
         let mut iter = self.rows_mut();
-        let mut prev = iter.next().unwrap();
+        let Some(mut prev) = iter.next() else {
+            return;
+        };
 
         for next in iter {
             action(prev, next);
@@ -188,6 +190,35 @@ impl<T> Grid<T> {
             .filter(move |dir| self.step_from_index(i, *dir).is_some())
     }
 
+    /// Step in any of the eight directions. Diagonal steps are taken as a
+    /// vertical step followed by a horizontal one, so they inherit the same
+    /// row-wrap protection as the cardinal steps
+    pub fn step8_from_index(&self, i: usize, dir: Compass8) -> Option<usize> {
+        let i = match dir.vertical() {
+            Some(v) => self.step_from_index(i, v)?,
+            None => i,
+        };
+
+        match dir.horizontal() {
+            Some(h) => self.step_from_index(i, h),
+            None => Some(i),
+        }
+    }
+
+    pub fn neighbors8(
+        &self,
+        i: usize,
+    ) -> impl Iterator<Item = (Compass8, usize)> + '_ {
+        Compass8::iter().filter_map(move |dir| {
+            self.step8_from_index(i, dir).map(move |j| (dir, j))
+        })
+    }
+
+    pub fn escaping8(&self, i: usize) -> impl Iterator<Item = Compass8> + '_ {
+        Compass8::iter()
+            .filter(move |dir| self.step8_from_index(i, *dir).is_some())
+    }
+
     pub fn min_dist(&self, from: usize, to: usize) -> usize {
         let col_dist = (from % self.width).abs_diff(to % self.width);
         let row_dist = (from / self.width).abs_diff(to / self.width);
@@ -230,7 +261,10 @@ impl<T> Grid<T> {
         ((i % self.width) as i32, (i / self.width) as i32)
     }
 
+    /// Unchecked conversion back to a flat index. Prefer
+    /// [`Grid::index_of`] when the coordinates could be out of bounds
     pub fn to_index(&self, col: i32, row: i32) -> usize {
+        debug_assert!(col >= 0 && row >= 0);
         col as usize + row as usize * self.width
     }
 }
@@ -240,26 +274,6 @@ where
     T: Copy,
 {
     pub fn quadruple(self) -> Self {
-        let mut data = Vec::with_capacity(self.data.len() * 4);
-        let width = self.width * 2;
-        let height = self.height * 2;
-
-        for _ in 0..2 {
-            for row in 0..self.height {
-                for _ in 0..2 {
-                    data.extend(
-                        self.data[(row * self.width)..((row + 1) * self.width)]
-                            .iter()
-                            .copied(),
-                    );
-                }
-            }
-        }
-
-        Self {
-            data,
-            height,
-            width,
-        }
+        self.tile(2, 2)
     }
 }
@@ -0,0 +1,242 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use strum::IntoEnumIterator;
+
+use crate::{Compass, Compass8, Grid};
+
+/// A [`Grid`] surrounded by a one cell border of sentinel values. Every real
+/// cell has all eight neighbors inside the storage, so stepping is just
+/// adding a fixed offset to the index with no bounds checks.
+///
+/// Indices taken and returned by this type are into the padded storage. Use
+/// [`PaddedGrid::to_padded`] and [`PaddedGrid::to_unpadded`] to convert to
+/// and from indices into the original grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaddedGrid<T> {
+    /// The padded storage, two cells wider and taller than the content
+    pub padded: Grid<T>,
+}
+
+impl<T> PaddedGrid<T>
+where
+    T: Clone,
+{
+    pub fn new(grid: &Grid<T>, sentinel: T) -> Self {
+        let stride = grid.width + 2;
+        let mut data = vec![sentinel.clone(); stride];
+
+        for row in grid.rows() {
+            data.push(sentinel.clone());
+            data.extend_from_slice(row);
+            data.push(sentinel.clone());
+        }
+
+        data.extend(std::iter::repeat_n(sentinel, stride));
+
+        Self {
+            padded: Grid::new(data, stride),
+        }
+    }
+
+    /// Parse the input like [`Grid::parse_lines`] and pad it with `sentinel`
+    pub fn parse(input: &str, sentinel: T) -> Self
+    where
+        T: From<char>,
+    {
+        Self::new(&Grid::parse_lines(input), sentinel)
+    }
+
+    /// Copy the content back out without the border
+    pub fn to_grid(&self) -> Grid<T> {
+        let data = self.rows().flatten().cloned().collect();
+        Grid::new(data, self.width())
+    }
+}
+
+impl<T> PaddedGrid<T> {
+    /// Width of the content, not counting the border
+    pub fn width(&self) -> usize {
+        self.padded.width - 2
+    }
+
+    /// Height of the content, not counting the border
+    pub fn height(&self) -> usize {
+        self.padded.height - 2
+    }
+
+    fn stride(&self) -> usize {
+        self.padded.width
+    }
+
+    /// Convert an index into the unpadded grid to one into the padded storage
+    pub fn to_padded(&self, i: usize) -> usize {
+        let (col, row) = (i % self.width(), i / self.width());
+        (row + 1) * self.stride() + col + 1
+    }
+
+    /// Convert a padded index back to one into the unpadded grid, or `None`
+    /// if it's part of the border
+    pub fn to_unpadded(&self, p: usize) -> Option<usize> {
+        let (col, row) = (p % self.stride(), p / self.stride());
+        let inside = (1..=self.width()).contains(&col)
+            && (1..=self.height()).contains(&row);
+
+        inside.then(|| (row - 1) * self.width() + col - 1)
+    }
+
+    pub fn is_sentinel(&self, p: usize) -> bool {
+        self.to_unpadded(p).is_none()
+    }
+
+    /// The padded index one step away. Only valid for non-border cells,
+    /// which always have a cell in every direction
+    pub fn step(&self, p: usize, dir: Compass) -> usize {
+        match dir {
+            Compass::N => p - self.stride(),
+            Compass::E => p + 1,
+            Compass::S => p + self.stride(),
+            Compass::W => p - 1,
+        }
+    }
+
+    pub fn step8(&self, p: usize, dir: Compass8) -> usize {
+        let p = dir.vertical().map_or(p, |v| self.step(p, v));
+        dir.horizontal().map_or(p, |h| self.step(p, h))
+    }
+
+    pub fn neighbors(
+        &self,
+        p: usize,
+    ) -> impl Iterator<Item = (Compass, usize)> + '_ {
+        Compass::iter().map(move |dir| (dir, self.step(p, dir)))
+    }
+
+    pub fn neighbors8(
+        &self,
+        p: usize,
+    ) -> impl Iterator<Item = (Compass8, usize)> + '_ {
+        Compass8::iter().map(move |dir| (dir, self.step8(p, dir)))
+    }
+
+    /// Padded indices of every real cell in row order
+    pub fn cells(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.width() * self.height()).map(|i| self.to_padded(i))
+    }
+
+    /// The rows of the content with the border stripped
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'_ [T]> + '_ {
+        let (width, height) = (self.width(), self.height());
+
+        self.padded
+            .data
+            .chunks(self.stride())
+            .skip(1)
+            .take(height)
+            .map(move |row| &row[1..=width])
+    }
+}
+
+impl<T> Index<usize> for PaddedGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: usize) -> &Self::Output {
+        &self.padded.data[p]
+    }
+}
+
+impl<T> IndexMut<usize> for PaddedGrid<T> {
+    fn index_mut(&mut self, p: usize) -> &mut Self::Output {
+        &mut self.padded.data[p]
+    }
+}
+
+impl<T> Display for PaddedGrid<T>
+where
+    char: From<T>,
+    T: Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row, cells) in self.rows().enumerate() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+
+            for t in cells {
+                write!(f, "{}", char::from(*t))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> PaddedGrid<T>
+where
+    char: From<T>,
+    T: Copy,
+{
+    pub fn print(&self) {
+        println!("{self}");
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    pub fn padded(&self, sentinel: T) -> PaddedGrid<T> {
+        PaddedGrid::new(self, sentinel)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Compass, Grid, PaddedGrid};
+
+    #[test]
+    fn translates_indices() {
+        let grid = Grid::<char>::parse_lines("abc\ndef");
+        let padded = grid.padded('#');
+
+        assert_eq!((padded.width(), padded.height()), (3, 2));
+        assert_eq!(padded.padded.data.len(), 20);
+
+        for i in 0..grid.data.len() {
+            let p = padded.to_padded(i);
+            assert_eq!(padded[p], grid.data[i]);
+            assert_eq!(padded.to_unpadded(p), Some(i));
+        }
+
+        assert!(padded.is_sentinel(0));
+        assert!(padded.is_sentinel(padded.to_padded(2) + 1));
+        assert_eq!(padded.to_grid(), grid);
+        assert_eq!(padded.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn steps_into_sentinels() {
+        let padded = PaddedGrid::parse("ab\ncd", '#');
+        let a = padded.to_padded(0);
+
+        let around = padded
+            .neighbors(a)
+            .map(|(_, p)| padded[p])
+            .collect::<String>();
+        assert_eq!(around, "#bc#");
+        assert_eq!(
+            padded
+                .neighbors8(a)
+                .filter(|(_, p)| padded[*p] == '#')
+                .count(),
+            5
+        );
+        assert_eq!(padded[padded.step(a, Compass::S)], 'c');
+        assert_eq!(
+            padded.cells().map(|p| padded[p]).collect::<String>(),
+            "abcd"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+use crate::Grid;
+
+/// An error which can be returned when parsing a [`Grid`]. Line and column
+/// numbers are 1-based to match what an editor shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError<E> {
+    Empty,
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        line: usize,
+        col: usize,
+        found: char,
+        error: E,
+    },
+}
+
+impl<E> Display for GridParseError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => f.write_str("grid input has no rows"),
+            GridParseError::RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has width {found} but expected width {expected}"
+            ),
+            GridParseError::InvalidCell {
+                line,
+                col,
+                found,
+                error,
+            } => write!(
+                f,
+                "invalid cell {found:?} at line {line}, column {col}: {error}"
+            ),
+        }
+    }
+}
+
+impl<E> Error for GridParseError<E> where E: Debug + Display {}
+
+impl<T> Grid<T>
+where
+    T: TryFrom<char>,
+{
+    /// Parse a rectangular block of characters into a grid. Both `\n` and
+    /// `\r\n` line endings are accepted and trailing blank lines are ignored,
+    /// but every other line must have the same width.
+    pub fn try_parse(input: &str) -> Result<Self, GridParseError<T::Error>> {
+        let lines = input.trim_end_matches(['\r', '\n']);
+
+        if lines.is_empty() {
+            return Err(GridParseError::Empty);
+        }
+
+        let mut width = None;
+        let mut data = Vec::with_capacity(lines.len());
+
+        for (row, line) in lines.lines().enumerate() {
+            let before = data.len();
+
+            for (col, c) in line.chars().enumerate() {
+                let cell = T::try_from(c).map_err(|error| {
+                    GridParseError::InvalidCell {
+                        line: row + 1,
+                        col: col + 1,
+                        found: c,
+                        error,
+                    }
+                })?;
+
+                data.push(cell);
+            }
+
+            let found = data.len() - before;
+            let expected = *width.get_or_insert(found);
+
+            if found != expected {
+                return Err(GridParseError::RaggedLine {
+                    line: row + 1,
+                    expected,
+                    found,
+                });
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self::new(data, width)),
+            _ => Err(GridParseError::Empty),
+        }
+    }
+}
+
+/// The error for a non-digit cell in [`Grid::try_parse_digits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotADigit;
+
+impl Error for NotADigit {}
+
+impl Display for NotADigit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a digit between 0 and 9")
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a rectangular block of bytes into a grid of raw bytes. Rows are
+    /// copied straight out of the input without any UTF-8 decoding. Line
+    /// endings are handled the same way as [`Grid::try_parse`]
+    pub fn try_parse_bytes(
+        input: &[u8],
+    ) -> Result<Self, GridParseError<Infallible>> {
+        let end = input
+            .iter()
+            .rposition(|b| *b != b'\n' && *b != b'\r')
+            .map_or(0, |i| i + 1);
+        let input = &input[..end];
+
+        if input.is_empty() {
+            return Err(GridParseError::Empty);
+        }
+
+        let first = input.split(|b| *b == b'\n').next().unwrap_or(input);
+        let width = first.strip_suffix(b"\r").unwrap_or(first).len();
+        let mut data = Vec::with_capacity(input.len());
+
+        for (row, line) in input.split(|b| *b == b'\n').enumerate() {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+            if line.len() != width {
+                return Err(GridParseError::RaggedLine {
+                    line: row + 1,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+
+            data.extend_from_slice(line);
+        }
+
+        match width {
+            0 => Err(GridParseError::Empty),
+            _ => Ok(Self::new(data, width)),
+        }
+    }
+
+    pub fn parse_bytes(input: &[u8]) -> Self {
+        Self::try_parse_bytes(input).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Parse a block of digits into a grid holding their values `0..=9`
+    pub fn try_parse_digits(
+        input: &[u8],
+    ) -> Result<Self, GridParseError<NotADigit>> {
+        let mut grid = Self::try_parse_bytes(input).map_err(|e| match e {
+            GridParseError::Empty => GridParseError::Empty,
+            GridParseError::RaggedLine {
+                line,
+                expected,
+                found,
+            } => GridParseError::RaggedLine {
+                line,
+                expected,
+                found,
+            },
+        })?;
+
+        for (i, cell) in grid.data.iter_mut().enumerate() {
+            if !cell.is_ascii_digit() {
+                return Err(GridParseError::InvalidCell {
+                    line: i / grid.width + 1,
+                    col: i % grid.width + 1,
+                    found: char::from(*cell),
+                    error: NotADigit,
+                });
+            }
+
+            *cell -= b'0';
+        }
+
+        Ok(grid)
+    }
+
+    pub fn parse_digits(input: &[u8]) -> Self {
+        Self::try_parse_digits(input).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{GridParseError, NotADigit};
+    use crate::Grid;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Wall(bool);
+
+    impl TryFrom<char> for Wall {
+        type Error = &'static str;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Wall(true)),
+                '.' => Ok(Wall(false)),
+                _ => Err("expected '#' or '.'"),
+            }
+        }
+    }
+
+    #[test]
+    fn tolerates_crlf_and_trailing_blank_lines() {
+        let grid = Grid::<Wall>::try_parse("#.\r\n.#\r\n\r\n\n").unwrap();
+
+        assert_eq!((grid.width, grid.height), (2, 2));
+    }
+
+    #[test]
+    fn reports_ragged_lines() {
+        assert_eq!(
+            Grid::<Wall>::try_parse("##\n#\n##"),
+            Err(GridParseError::RaggedLine {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn reports_invalid_cells() {
+        assert_eq!(
+            Grid::<Wall>::try_parse("##\n#x"),
+            Err(GridParseError::InvalidCell {
+                line: 2,
+                col: 2,
+                found: 'x',
+                error: "expected '#' or '.'"
+            })
+        );
+        assert_eq!(Grid::<Wall>::try_parse("\n"), Err(GridParseError::Empty));
+    }
+
+    #[test]
+    fn parses_digits_from_bytes() {
+        let grid = Grid::parse_digits(b"0123\r\n4567\r\n");
+
+        assert_eq!(grid, Grid::new((0..8).collect(), 4));
+        assert_eq!(
+            Grid::try_parse_digits(b"01\n2x\n"),
+            Err(GridParseError::InvalidCell {
+                line: 2,
+                col: 2,
+                found: 'x',
+                error: NotADigit
+            })
+        );
+    }
+}
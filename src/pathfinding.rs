@@ -0,0 +1,509 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use crate::{Compass, Grid, TV4};
+
+/// The result of a shortest path search. This holds the best distance to
+/// every state that was reached along with every optimal predecessor of that
+/// state, so single paths, all paths and path counts can be recovered
+/// afterwards.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<S: Default> {
+    pub dist: HashMap<S, usize>,
+    preds: HashMap<S, TV4<S>>,
+}
+
+impl<S> ShortestPaths<S>
+where
+    S: Copy + Eq + Hash + Default,
+{
+    fn with_starts(starts: impl IntoIterator<Item = S>) -> Self {
+        let mut result = Self {
+            dist: HashMap::new(),
+            preds: HashMap::new(),
+        };
+
+        for start in starts {
+            result.dist.insert(start, 0);
+            result.preds.insert(start, TV4::new());
+        }
+
+        result
+    }
+
+    /// Record that `next` can be reached at cost `cost` via `from`. Returns
+    /// true if this is a strict improvement on what we had before. Ties are
+    /// only kept for moves that cost something, as zero cost moves would let
+    /// two states become each other's predecessor
+    fn relax(&mut self, from: S, next: S, cost: usize) -> bool {
+        let free = self.dist.get(&from) == Some(&cost);
+
+        match self.dist.get(&next).copied() {
+            Some(best) if best < cost => false,
+            Some(best) if best == cost => {
+                if !free {
+                    self.preds.entry(next).or_default().push(from);
+                }
+                false
+            }
+            _ => {
+                self.dist.insert(next, cost);
+                self.preds.insert(next, [from].into_iter().collect());
+                true
+            }
+        }
+    }
+
+    pub fn distance(&self, state: S) -> Option<usize> {
+        self.dist.get(&state).copied()
+    }
+
+    /// The goal with the smallest distance out of those given
+    pub fn best_of(
+        &self,
+        goals: impl IntoIterator<Item = S>,
+    ) -> Option<(S, usize)> {
+        goals
+            .into_iter()
+            .filter_map(|goal| self.distance(goal).map(|d| (goal, d)))
+            .min_by_key(|(_, d)| *d)
+    }
+
+    /// One shortest path from a start state to the goal (inclusive at both
+    /// ends)
+    pub fn path_to(&self, goal: S) -> Option<Vec<S>> {
+        self.distance(goal)?;
+
+        let mut path = vec![goal];
+        let mut curr = goal;
+
+        while let Some(&prev) = self.preds.get(&curr).and_then(|p| p.first()) {
+            path.push(prev);
+            curr = prev;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path to the goal. The number of these can grow
+    /// exponentially, so prefer [`ShortestPaths::count_paths_to`] or
+    /// [`ShortestPaths::on_optimal_paths`] when those are enough
+    pub fn all_paths_to(&self, goal: S) -> Vec<Vec<S>> {
+        if self.distance(goal).is_none() {
+            return vec![];
+        }
+
+        let mut result = vec![];
+        let mut stack = vec![vec![goal]];
+
+        while let Some(path) = stack.pop() {
+            let last = *path.last().unwrap();
+            let preds = self.preds.get(&last).map(|p| p.as_slice());
+
+            match preds {
+                Some(preds) if !preds.is_empty() => {
+                    for prev in preds {
+                        let mut next = path.clone();
+                        next.push(*prev);
+                        stack.push(next);
+                    }
+                }
+                _ => {
+                    let mut path = path;
+                    path.reverse();
+                    result.push(path);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The number of distinct shortest paths from any start to the goal
+    pub fn count_paths_to(&self, goal: S) -> usize {
+        if self.distance(goal).is_none() {
+            return 0;
+        }
+
+        // Post-order over the predecessor graph with an explicit stack, as
+        // paths through long corridors are far too deep to recurse over
+        let mut counts = HashMap::new();
+        let mut stack = vec![(goal, false)];
+
+        while let Some((state, expanded)) = stack.pop() {
+            if counts.contains_key(&state) {
+                continue;
+            }
+
+            let preds =
+                self.preds.get(&state).map_or(&[][..], |p| p.as_slice());
+
+            if preds.is_empty() {
+                counts.insert(state, 1);
+            } else if expanded {
+                let total = preds.iter().map(|prev| counts[prev]).sum();
+                counts.insert(state, total);
+            } else {
+                stack.push((state, true));
+                stack.extend(
+                    preds
+                        .iter()
+                        .filter(|prev| !counts.contains_key(*prev))
+                        .map(|prev| (*prev, false)),
+                );
+            }
+        }
+
+        counts[&goal]
+    }
+
+    /// Every state that lies on at least one shortest path to the closest of
+    /// the given goals
+    pub fn on_optimal_paths(
+        &self,
+        goals: impl IntoIterator<Item = S>,
+    ) -> HashSet<S> {
+        let goals = goals
+            .into_iter()
+            .filter_map(|goal| self.distance(goal).map(|d| (goal, d)))
+            .collect::<Vec<_>>();
+
+        let Some(best) = goals.iter().map(|(_, d)| *d).min() else {
+            return HashSet::new();
+        };
+
+        let mut result = HashSet::new();
+        let mut stack = goals
+            .into_iter()
+            .filter(|(_, d)| *d == best)
+            .map(|(goal, _)| goal)
+            .collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            if result.insert(state) {
+                if let Some(preds) = self.preds.get(&state) {
+                    stack.extend(preds.iter().copied());
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Entry in the dijkstra/A* priority queue ordered so that the lowest
+/// priority is popped first
+struct Frontier<S> {
+    priority: usize,
+    cost: usize,
+    state: S,
+}
+
+impl<S> PartialEq for Frontier<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S> Eq for Frontier<S> {}
+
+impl<S> PartialOrd for Frontier<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Frontier<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Breadth first search where every step costs 1. The search stops once
+/// every state at the same distance as the first goal found is settled, so
+/// all optimal paths to that goal are kept. Pass `|_| false` as `is_goal` to
+/// explore everything reachable
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(S) -> I,
+    mut is_goal: impl FnMut(S) -> bool,
+) -> ShortestPaths<S>
+where
+    S: Copy + Eq + Hash + Default,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = ShortestPaths::with_starts(starts);
+    let mut queue = result.dist.keys().copied().collect::<VecDeque<_>>();
+    let mut limit = usize::MAX;
+
+    while let Some(state) = queue.pop_front() {
+        let dist = result.dist[&state];
+
+        if dist >= limit {
+            break;
+        }
+
+        if is_goal(state) {
+            limit = dist;
+            continue;
+        }
+
+        for next in successors(state) {
+            if result.relax(state, next, dist + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm over arbitrary states. `successors` yields each
+/// reachable state along with the cost of moving there. Has the same goal
+/// semantics as [`bfs`]
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(S) -> I,
+    mut is_goal: impl FnMut(S) -> bool,
+) -> ShortestPaths<S>
+where
+    S: Copy + Eq + Hash + Default,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut result = ShortestPaths::with_starts(starts);
+    let mut heap = result
+        .dist
+        .keys()
+        .map(|state| Frontier {
+            priority: 0,
+            cost: 0,
+            state: *state,
+        })
+        .collect::<BinaryHeap<_>>();
+    let mut limit = usize::MAX;
+
+    while let Some(Frontier { cost, state, .. }) = heap.pop() {
+        if cost > result.dist[&state] {
+            continue;
+        }
+
+        if cost > limit {
+            break;
+        }
+
+        if is_goal(state) {
+            limit = cost;
+            continue;
+        }
+
+        for (next, step_cost) in successors(state) {
+            let next_cost = cost + step_cost;
+
+            if result.relax(state, next, next_cost) {
+                heap.push(Frontier {
+                    priority: next_cost,
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// A* search for a single shortest path. The heuristic must never
+/// overestimate the remaining cost for the result to be optimal
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(S) -> I,
+    mut heuristic: impl FnMut(S) -> usize,
+    mut is_goal: impl FnMut(S) -> bool,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Copy + Eq + Hash + Default,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut result = ShortestPaths::with_starts([start]);
+    let mut heap = BinaryHeap::from([Frontier {
+        priority: heuristic(start),
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Frontier { cost, state, .. }) = heap.pop() {
+        if cost > result.dist[&state] {
+            continue;
+        }
+
+        if is_goal(state) {
+            return result.path_to(state).map(|path| (cost, path));
+        }
+
+        for (next, step_cost) in successors(state) {
+            let next_cost = cost + step_cost;
+
+            if result.relax(state, next, next_cost) {
+                heap.push(Frontier {
+                    priority: next_cost + heuristic(next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+impl<T> Grid<T> {
+    /// Unit cost search from `start` over every cell reachable by moves that
+    /// `can_move(from, to)` allows
+    pub fn bfs(
+        &self,
+        start: usize,
+        can_move: impl Fn(usize, usize) -> bool,
+    ) -> ShortestPaths<usize> {
+        bfs(
+            [start],
+            |i| {
+                self.neighbors(i)
+                    .map(|(_, j)| j)
+                    .filter(|j| can_move(i, *j))
+                    .collect::<TV4<_>>()
+            },
+            |_| false,
+        )
+    }
+
+    /// Weighted search from `start` where `cost(from, to)` gives the cost of
+    /// moving between neighboring cells or `None` if the move is blocked
+    pub fn dijkstra(
+        &self,
+        start: usize,
+        cost: impl Fn(usize, usize) -> Option<usize>,
+    ) -> ShortestPaths<usize> {
+        dijkstra(
+            [start],
+            |i| {
+                self.neighbors(i)
+                    .filter_map(|(_, j)| cost(i, j).map(|c| (j, c)))
+                    .collect::<TV4<_>>()
+            },
+            |_| false,
+        )
+    }
+
+    /// A* from `start` to `goal` using manhattan distance as the heuristic,
+    /// which is admissible as long as every move costs at least 1
+    pub fn astar(
+        &self,
+        start: usize,
+        goal: usize,
+        cost: impl Fn(usize, usize) -> Option<usize>,
+    ) -> Option<(usize, Vec<usize>)> {
+        astar(
+            start,
+            |i| {
+                self.neighbors(i)
+                    .filter_map(|(_, j)| cost(i, j).map(|c| (j, c)))
+                    .collect::<TV4<_>>()
+            },
+            |i| self.min_dist(i, goal),
+            |i| i == goal,
+        )
+    }
+
+    /// Weighted search over states that include the current heading. From
+    /// each state you can either move forward for `cost(from, to)` or turn 90
+    /// degrees in place for `turn_cost`
+    pub fn dijkstra_with_heading(
+        &self,
+        start: usize,
+        heading: Compass,
+        turn_cost: usize,
+        cost: impl Fn(usize, usize) -> Option<usize>,
+    ) -> ShortestPaths<(usize, Compass)> {
+        dijkstra(
+            [(start, heading)],
+            |(i, dir)| {
+                let forward = self
+                    .step_from_index(i, dir)
+                    .and_then(|j| cost(i, j).map(|c| ((j, dir), c)));
+
+                let turns = [dir.turn_left(), dir.turn_right()]
+                    .map(|turned| ((i, turned), turn_cost));
+
+                forward.into_iter().chain(turns)
+            },
+            |_| false,
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use crate::{Compass, Grid};
+
+    #[test]
+    fn bfs_counts_optimal_paths() {
+        let grid = Grid::<char>::parse_lines("....\n.##.\n....");
+        let result = grid.bfs(0, |_, j| grid.data[j] != '#');
+
+        assert_eq!(result.distance(11), Some(5));
+        assert_eq!(result.count_paths_to(11), 2);
+        assert_eq!(result.all_paths_to(11).len(), 2);
+        assert_eq!(result.path_to(11).map(|p| p.len()), Some(6));
+        assert_eq!(result.on_optimal_paths([11]).len(), 10);
+    }
+
+    #[test]
+    fn counts_paths_down_long_corridors() {
+        let grid = Grid::new(vec!['.'; 300_000], 300_000);
+        let result = grid.bfs(0, |_, _| true);
+
+        assert_eq!(result.count_paths_to(299_999), 1);
+    }
+
+    #[test]
+    fn zero_cost_moves_terminate() {
+        let grid = Grid::<char>::parse_lines("...");
+        let result = grid.dijkstra(0, |_, _| Some(0));
+
+        assert_eq!(result.distance(2), Some(0));
+        assert_eq!(result.path_to(2), Some(vec![0, 1, 2]));
+        assert_eq!(result.all_paths_to(2), [vec![0, 1, 2]]);
+        assert_eq!(result.count_paths_to(2), 1);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let grid = Grid::<char>::parse_lines("....\n.##.\n....");
+        let cost = |_, j: usize| (grid.data[j] != '#').then_some(j % 3 + 1);
+
+        let expected = grid.dijkstra(0, cost).distance(10);
+        let (found, path) = grid.astar(0, 10, cost).unwrap();
+
+        assert_eq!(Some(found), expected);
+        assert_eq!(path.first(), Some(&0));
+        assert_eq!(path.last(), Some(&10));
+    }
+
+    #[test]
+    fn heading_turns_cost_extra() {
+        let grid = Grid::<char>::parse_lines("....\n.##.\n....");
+        let result = grid.dijkstra_with_heading(0, Compass::E, 1000, |_, j| {
+            (grid.data[j] != '#').then_some(1)
+        });
+
+        let best = result.best_of(Compass::iter().map(|d| (11, d)));
+
+        assert_eq!(best.map(|(_, d)| d), Some(1005));
+    }
+}
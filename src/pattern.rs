@@ -0,0 +1,209 @@
+use strum::IntoEnumIterator;
+
+use crate::{Compass8, Grid, GridView};
+
+/// One of the eight ways a pattern can be laid onto a grid: some number of
+/// clockwise quarter turns, optionally after mirroring left to right
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub quarter_turns: u8,
+    pub flipped: bool,
+}
+
+impl Orientation {
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        [false, true].into_iter().flat_map(|flipped| {
+            (0..4).map(move |quarter_turns| Orientation {
+                quarter_turns,
+                flipped,
+            })
+        })
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn orient(self, orientation: Orientation) -> Self {
+        let view = if orientation.flipped {
+            self.flip_horizontal()
+        } else {
+            self
+        };
+
+        (0..orientation.quarter_turns).fold(view, |v, _| v.rotate_cw())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Every `width` x `height` window of the grid along with the index of
+    /// its top-left cell
+    pub fn windows(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, GridView<'_, T>)> + '_ {
+        let cols = (self.width + 1).saturating_sub(width);
+        let rows = (self.height + 1).saturating_sub(height);
+
+        (0..rows).flat_map(move |row| {
+            (0..cols).map(move |col| {
+                (
+                    row * self.width + col,
+                    self.view().sub_view(col, row, width, height),
+                )
+            })
+        })
+    }
+
+    /// True if every cell of `pattern` satisfies `matches` against the cell it
+    /// lands on when the pattern's top-left is placed at `at`
+    fn pattern_matches_at<P>(
+        &self,
+        pattern: &GridView<'_, P>,
+        at: usize,
+        matches: &impl Fn(&P, &T) -> bool,
+    ) -> bool {
+        let (col, row) = (at % self.width, at / self.width);
+
+        pattern.rows().enumerate().all(|(r, cells)| {
+            cells.enumerate().all(|(c, p)| {
+                matches(p, &self.data[(row + r) * self.width + col + c])
+            })
+        })
+    }
+
+    /// Indices of the top-left cell of every placement of `pattern` where
+    /// each pattern cell satisfies `matches` against the grid cell under it
+    pub fn find_pattern_by<'a, P>(
+        &'a self,
+        pattern: GridView<'a, P>,
+        matches: impl Fn(&P, &T) -> bool + 'a,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let cols = (self.width + 1).saturating_sub(pattern.width);
+        let rows = (self.height + 1).saturating_sub(pattern.height);
+
+        (0..rows)
+            .flat_map(move |row| {
+                (0..cols).map(move |col| row * self.width + col)
+            })
+            .filter(move |at| self.pattern_matches_at(&pattern, *at, &matches))
+    }
+
+    /// Like [`Grid::find_pattern_by`] but tries the pattern in every
+    /// rotation and reflection. Orientations that produce the same pattern
+    /// as an earlier one are skipped so symmetric patterns aren't counted
+    /// twice
+    pub fn find_pattern_oriented_by<'a, P>(
+        &'a self,
+        pattern: &'a Grid<P>,
+        matches: impl Fn(&P, &T) -> bool + Copy + 'a,
+    ) -> impl Iterator<Item = (usize, Orientation)> + 'a
+    where
+        P: PartialEq,
+    {
+        let mut distinct: Vec<(Orientation, GridView<'a, P>)> = vec![];
+
+        for orientation in Orientation::all() {
+            let view = pattern.view().orient(orientation);
+            let duplicate = distinct.iter().any(|(_, seen)| {
+                (seen.width, seen.height) == (view.width, view.height)
+                    && seen.iter().eq(view.iter())
+            });
+
+            if !duplicate {
+                distinct.push((orientation, view));
+            }
+        }
+
+        distinct.into_iter().flat_map(move |(orientation, view)| {
+            self.find_pattern_by(view, matches)
+                .map(move |at| (at, orientation))
+        })
+    }
+
+    /// Every cell and direction from which reading along the grid yields
+    /// `seq`. An empty `seq` doesn't match anywhere
+    pub fn find_sequence<'a>(
+        &'a self,
+        seq: &'a [T],
+    ) -> impl Iterator<Item = (usize, Compass8)> + 'a
+    where
+        T: PartialEq,
+    {
+        let cells = if seq.is_empty() { 0 } else { self.data.len() };
+
+        (0..cells)
+            .flat_map(|i| Compass8::iter().map(move |dir| (i, dir)))
+            .filter(move |(i, dir)| {
+                let mut at = Some(*i);
+
+                seq.iter().all(|t| match at {
+                    Some(j) if self.data[j] == *t => {
+                        at = self.step8_from_index(j, *dir);
+                        true
+                    }
+                    _ => false,
+                })
+            })
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    /// Find every placement of a pattern where `None` cells are wildcards
+    pub fn find_pattern<'a>(
+        &'a self,
+        pattern: &'a Grid<Option<T>>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.find_pattern_by(pattern.view(), |p, t| {
+            p.as_ref().is_none_or(|p| p == t)
+        })
+    }
+
+    /// Find every placement of a wildcard pattern in any orientation
+    pub fn find_pattern_oriented<'a>(
+        &'a self,
+        pattern: &'a Grid<Option<T>>,
+    ) -> impl Iterator<Item = (usize, Orientation)> + 'a {
+        self.find_pattern_oriented_by(pattern, |p, t| {
+            p.as_ref().is_none_or(|p| p == t)
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::Grid;
+
+    const WORDS: &str = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\n\
+                         XMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\n\
+                         MAMMMXMMMM\nMXMXAXMASX";
+
+    #[test]
+    fn finds_words_in_every_direction() {
+        let grid = Grid::<char>::parse_lines(WORDS);
+        let xmas = "XMAS".chars().collect::<Vec<_>>();
+
+        assert_eq!(grid.find_sequence(&xmas).count(), 18);
+        assert_eq!(grid.find_sequence(&[]).count(), 0);
+        assert_eq!(grid.find_sequence(&['X']).count(), 19 * 8);
+    }
+
+    #[test]
+    fn finds_patterns_in_every_orientation() {
+        let grid = Grid::<char>::parse_lines(WORDS);
+        let pattern = Grid::<char>::parse_lines("M.S\n.A.\nM.S");
+        let pattern = Grid::new(
+            pattern
+                .data
+                .iter()
+                .map(|c| (*c != '.').then_some(*c))
+                .collect(),
+            pattern.width,
+        );
+
+        assert_eq!(grid.find_pattern_oriented(&pattern).count(), 9);
+        assert_eq!(grid.windows(3, 3).count(), 64);
+    }
+}
@@ -0,0 +1,246 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::{Compass, Compass8, Grid};
+
+/// A signed column/row coordinate on a [`Grid`]. Points are allowed to lie
+/// outside the grid, so use [`Grid::index_of`] to get back to a flat index.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub col: i32,
+    pub row: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { col: 0, row: 0 };
+
+    pub const fn new(col: i32, row: i32) -> Self {
+        Self { col, row }
+    }
+
+    /// The unit offset for a single step in the given direction
+    pub fn unit(dir: Compass8) -> Self {
+        use Compass8 as D;
+
+        match dir {
+            D::N => Self::new(0, -1),
+            D::NE => Self::new(1, -1),
+            D::E => Self::new(1, 0),
+            D::SE => Self::new(1, 1),
+            D::S => Self::new(0, 1),
+            D::SW => Self::new(-1, 1),
+            D::W => Self::new(-1, 0),
+            D::NW => Self::new(-1, -1),
+        }
+    }
+
+    pub fn step(self, dir: Compass) -> Self {
+        self + Self::unit(dir.into())
+    }
+
+    pub fn step8(self, dir: Compass8) -> Self {
+        self + Self::unit(dir)
+    }
+
+    pub fn manhattan(&self, other: &Self) -> u32 {
+        self.col.abs_diff(other.col) + self.row.abs_diff(other.row)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> u32 {
+        self.col
+            .abs_diff(other.col)
+            .max(self.row.abs_diff(other.row))
+    }
+
+    /// True if the two points are one cardinal step apart
+    pub fn is_neighbor(&self, other: &Self) -> bool {
+        self.manhattan(other) == 1
+    }
+
+    /// True if the two points are one step apart including diagonals
+    pub fn is_neighbor8(&self, other: &Self) -> bool {
+        self.chebyshev(other) == 1
+    }
+
+    /// True if the point lies on the edge of the box with the given smallest
+    /// and largest corners, like those from [`crate::SparseGrid::bounds`]
+    pub fn is_border_of(&self, (min, max): (Point, Point)) -> bool {
+        let inside = (min.col..=max.col).contains(&self.col)
+            && (min.row..=max.row).contains(&self.row);
+
+        inside
+            && (self.col == min.col
+                || self.row == min.row
+                || self.col == max.col
+                || self.row == max.row)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((col, row): (i32, i32)) -> Self {
+        Self::new(col, row)
+    }
+}
+
+impl From<Point> for (i32, i32) {
+    fn from(p: Point) -> Self {
+        (p.col, p.row)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.col + rhs.col, self.row + rhs.row)
+    }
+}
+
+impl Add<Compass> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Compass) -> Self::Output {
+        self.step(rhs)
+    }
+}
+
+impl Add<Compass8> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Compass8) -> Self::Output {
+        self.step8(rhs)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(self.col - rhs.col, self.row - rhs.row)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Point::new(self.col * rhs, self.row * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.col, -self.row)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn contains_point(&self, p: Point) -> bool {
+        p.col >= 0
+            && p.row >= 0
+            && (p.col as usize) < self.width
+            && (p.row as usize) < self.height
+    }
+
+    /// Get the flat index of the given point if it lies within the grid
+    pub fn index_of(&self, p: Point) -> Option<usize> {
+        self.contains_point(p)
+            .then(|| p.col as usize + p.row as usize * self.width)
+    }
+
+    pub fn point_of(&self, i: usize) -> Point {
+        self.to_col_row(i).into()
+    }
+
+    pub fn at_point(&self, p: Point) -> Option<&'_ T> {
+        self.index_of(p).and_then(|i| self.at_index(i))
+    }
+
+    pub fn step_from_point(&self, p: Point, dir: Compass) -> Option<Point> {
+        Some(p + dir).filter(|next| self.contains_point(*next))
+    }
+
+    pub fn point_neighbors(
+        &self,
+        p: Point,
+    ) -> impl Iterator<Item = (Compass, Point)> + '_ {
+        self.index_of(p).into_iter().flat_map(move |i| {
+            self.neighbors(i).map(|(dir, j)| (dir, self.point_of(j)))
+        })
+    }
+
+    pub fn is_border_point(&self, p: Point) -> bool {
+        self.index_of(p).is_some_and(|i| self.is_border(i))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Compass, Compass8, Grid, Point};
+
+    #[test]
+    fn converts_indices() {
+        let grid = Grid::<char>::parse_lines("abc\ndef");
+
+        for i in 0..grid.data.len() {
+            assert_eq!(grid.index_of(grid.point_of(i)), Some(i));
+        }
+
+        assert_eq!(grid.point_of(5), Point::new(2, 1));
+        assert_eq!(grid.index_of(Point::new(-1, 0)), None);
+        assert_eq!(grid.index_of(Point::new(0, -1)), None);
+        assert_eq!(grid.index_of(Point::new(3, 0)), None);
+        assert_eq!(grid.index_of(Point::new(0, 2)), None);
+        assert_eq!(grid.at_point(Point::new(1, 1)), Some(&'e'));
+        assert_eq!(grid.at_point(Point::new(1, 2)), None);
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(-2, 3), Point::new(1, -1));
+
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(b.manhattan(&a), 7);
+        assert!(a.is_neighbor(&(a + Compass::W)));
+        assert!(!a.is_neighbor(&(a + Compass8::NE)));
+        assert!(a.is_neighbor8(&(a + Compass8::NE)));
+        assert_eq!(-(b - a) * 2, Point::new(-6, 8));
+    }
+
+    #[test]
+    fn neighbors_on_the_border() {
+        let grid = Grid::<char>::parse_lines("abc\ndef");
+        let around = |p| {
+            grid.point_neighbors(p)
+                .map(|(dir, q)| (dir, q.into()))
+                .collect::<Vec<(Compass, (i32, i32))>>()
+        };
+
+        assert_eq!(
+            around(Point::ORIGIN),
+            [(Compass::E, (1, 0)), (Compass::S, (0, 1))]
+        );
+        assert_eq!(
+            around(Point::new(2, 1)),
+            [(Compass::N, (2, 0)), (Compass::W, (1, 1))]
+        );
+        assert!(around(Point::new(-1, 0)).is_empty());
+        assert_eq!(grid.step_from_point(Point::new(2, 0), Compass::E), None);
+        assert!(grid.is_border_point(Point::new(1, 0)));
+        assert!(!grid.is_border_point(Point::new(5, 5)));
+    }
+}
@@ -0,0 +1,230 @@
+use std::ops::Range;
+
+use num_traits::PrimInt;
+
+use crate::intersection;
+
+/// A set of integers stored as sorted, disjoint half-open ranges. Ranges
+/// that overlap or touch are merged as they're added, so every value in the
+/// set belongs to exactly one range and there's always a gap between ranges
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T> RangeSet<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The total number of values in the set
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |total, r| total + (r.end - r.start))
+    }
+
+    /// The disjoint ranges making up the set in ascending order
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &'_ Range<T>> + '_ {
+        self.ranges.iter()
+    }
+
+    /// Every value in the set in ascending order
+    pub fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.ranges.iter().flat_map(|r| {
+            let end = r.end;
+            std::iter::successors(Some(r.start), move |x| {
+                Some(*x + T::one()).filter(|x| *x < end)
+            })
+        })
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// True if every value of `range` is in the set
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.start >= range.end {
+            return true;
+        }
+
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Add every value of `range`, merging it with any ranges it overlaps or
+    /// touches
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|r| r.end < range.start);
+        let hi = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if lo < hi {
+            self.ranges[lo].start.min(range.start)
+                ..self.ranges[hi - 1].end.max(range.end)
+        } else {
+            range
+        };
+
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    /// Take every value of `range` out of the set, splitting a range in two
+    /// if it's removed from the middle
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|r| r.end <= range.start);
+        let hi = self.ranges.partition_point(|r| r.start < range.end);
+
+        if lo >= hi {
+            return;
+        }
+
+        let (first, last) = (&self.ranges[lo], &self.ranges[hi - 1]);
+        let before = first.start..range.start;
+        let after = range.end..last.end;
+        let kept = [before, after].into_iter().filter(|r| r.start < r.end);
+
+        self.ranges.splice(lo..hi, kept.collect::<Vec<_>>());
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        other.iter().for_each(|r| result.insert(r.clone()));
+        result
+    }
+
+    /// Values that are in this set but not in `other`
+    pub fn subtract(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        other.iter().for_each(|r| result.remove(r.clone()));
+        result
+    }
+
+    /// Values that are in both sets
+    pub fn intersect(&self, other: &Self) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+
+        while i < a.len() && j < b.len() {
+            ranges.extend(intersection(&a[i], &b[j]));
+
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// The ranges within `bounds` that aren't in the set
+    pub fn gaps(&self, bounds: Range<T>) -> Self {
+        Self::from(bounds).subtract(self)
+    }
+}
+
+impl<T> From<Range<T>> for RangeSet<T>
+where
+    T: PrimInt,
+{
+    fn from(range: Range<T>) -> Self {
+        let mut result = Self::new();
+        result.insert(range);
+        result
+    }
+}
+
+impl<T> FromIterator<Range<T>> for RangeSet<T>
+where
+    T: PrimInt,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut result = Self::new();
+        iter.into_iter().for_each(|r| result.insert(r));
+        result
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RangeSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::RangeSet;
+
+    #[test]
+    fn merges_overlapping_and_touching() {
+        let fresh = [(3, 5), (10, 14), (16, 20), (12, 18)]
+            .into_iter()
+            .map(|(a, b)| a..b + 1)
+            .collect::<RangeSet<u64>>();
+
+        assert_eq!(fresh.ranges(), [3..6, 10..21]);
+        assert_eq!(fresh.len(), 14);
+        assert_eq!(
+            [1, 5, 8, 11, 17, 32]
+                .into_iter()
+                .filter(|id| fresh.contains(*id))
+                .count(),
+            3
+        );
+        assert!(fresh.contains_range(&(12..20)));
+        assert!(!fresh.contains_range(&(4..11)));
+
+        let mut touching = RangeSet::from(0..5);
+        touching.insert(5..7);
+        assert_eq!(touching, RangeSet::from(0..7));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = RangeSet::from_iter([0..10, 20..30]);
+        let b = RangeSet::from(5..25);
+
+        assert_eq!(a.union(&b), RangeSet::from(0..30));
+        assert_eq!(a.intersect(&b).ranges(), [5..10, 20..25]);
+        assert_eq!(a.subtract(&b).ranges(), [0..5, 25..30]);
+        assert_eq!(a.gaps(-5..35).ranges(), [-5..0, 10..20, 30..35]);
+
+        let mut split = RangeSet::from(0..10);
+        split.remove(3..6);
+        assert_eq!(split.ranges(), [0..3, 6..10]);
+        assert_eq!(split.values().collect::<Vec<i32>>(), [0, 1, 2, 6, 7, 8, 9]);
+    }
+}
@@ -0,0 +1,222 @@
+use strum::IntoEnumIterator;
+
+use crate::{Compass, Grid};
+
+/// Connected component labeling of a grid. Cells that don't belong to any
+/// region (because they were excluded by a predicate) are labeled `None`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    pub labels: Grid<Option<usize>>,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RegionStats {
+    pub area: usize,
+    pub perimeter: usize,
+    pub sides: usize,
+    pub corners: usize,
+}
+
+impl Regions {
+    pub fn label_of(&self, i: usize) -> Option<usize> {
+        self.labels.data.get(i).copied().flatten()
+    }
+
+    pub fn same_region(&self, i1: usize, i2: usize) -> bool {
+        self.label_of(i1)
+            .is_some_and(|label| self.label_of(i2) == Some(label))
+    }
+
+    pub fn cells(&self, region: usize) -> impl Iterator<Item = usize> + '_ {
+        self.labels
+            .data
+            .iter()
+            .enumerate()
+            .filter(move |(_, label)| **label == Some(region))
+            .map(|(i, _)| i)
+    }
+
+    /// True if moving from `i` in `dir` crosses the edge of its region
+    fn is_fence(&self, i: usize, dir: Compass) -> bool {
+        self.labels
+            .step_from_index(i, dir)
+            .is_none_or(|j| !self.same_region(i, j))
+    }
+
+    /// Compute the area, perimeter, number of straight sides and number of
+    /// corners of every region indexed by region label
+    pub fn stats(&self) -> Vec<RegionStats> {
+        let mut result = vec![RegionStats::default(); self.count];
+
+        for (i, label) in self.labels.data.iter().enumerate() {
+            let Some(label) = label else {
+                continue;
+            };
+
+            let stats = &mut result[*label];
+            stats.area += 1;
+
+            for dir in Compass::iter() {
+                if !self.is_fence(i, dir) {
+                    continue;
+                }
+
+                stats.perimeter += 1;
+
+                // Only count a side once, from the cell at its
+                // counter-clockwise end
+                let continues = self
+                    .labels
+                    .step_from_index(i, dir.turn_left())
+                    .is_some_and(|j| {
+                        self.same_region(i, j) && self.is_fence(j, dir)
+                    });
+
+                if !continues {
+                    stats.sides += 1;
+                }
+
+                let turned = dir.turn_right();
+                let outer = self.is_fence(i, turned);
+                let inner = !outer
+                    && self
+                        .labels
+                        .step_from_index(i, turned)
+                        .and_then(|j| self.labels.step_from_index(j, dir))
+                        .is_some_and(|j| self.same_region(i, j));
+
+                if outer || inner {
+                    stats.corners += 1;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl<T> Grid<T> {
+    /// Every cell reachable from `start` by moving between neighbors for which
+    /// `connected(from, to)` holds, in the order they were found
+    pub fn flood_fill(
+        &self,
+        start: usize,
+        connected: impl Fn(usize, usize) -> bool,
+    ) -> Vec<usize> {
+        let mut seen = vec![false; self.data.len()];
+        let mut result = vec![start];
+        let mut stack = vec![start];
+
+        seen[start] = true;
+
+        while let Some(i) = stack.pop() {
+            for (_, j) in self.neighbors(i) {
+                if !seen[j] && connected(i, j) {
+                    seen[j] = true;
+                    result.push(j);
+                    stack.push(j);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Label connected regions of cells that are included by `include` and
+    /// joined to their neighbors by `same`
+    pub fn label_regions_by(
+        &self,
+        include: impl Fn(&T) -> bool,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> Regions {
+        let mut labels = Grid::new(vec![None; self.data.len()], self.width);
+        let mut count = 0;
+
+        for start in 0..self.data.len() {
+            if labels.data[start].is_some() || !include(&self.data[start]) {
+                continue;
+            }
+
+            // Flood the region using the labels as the visited set, so each
+            // region only costs as much as the cells in it
+            let mut stack = vec![start];
+            labels.data[start] = Some(count);
+
+            while let Some(i) = stack.pop() {
+                for (_, j) in self.neighbors(i) {
+                    if labels.data[j].is_none()
+                        && include(&self.data[j])
+                        && same(&self.data[i], &self.data[j])
+                    {
+                        labels.data[j] = Some(count);
+                        stack.push(j);
+                    }
+                }
+            }
+
+            count += 1;
+        }
+
+        Regions { labels, count }
+    }
+
+    /// Label connected regions of cells matching the predicate
+    pub fn label_regions_where(&self, include: impl Fn(&T) -> bool) -> Regions {
+        self.label_regions_by(include, |_, _| true)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    /// Label connected regions of equal-valued cells
+    pub fn label_regions(&self) -> Regions {
+        self.label_regions_by(|_| true, |a, b| a == b)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Grid, RegionStats};
+
+    #[test]
+    fn region_stats() {
+        let grid = Grid::<char>::parse_lines("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = grid.label_regions();
+        let stats = regions.stats();
+
+        assert_eq!(regions.count, 5);
+        assert_eq!(
+            stats[regions.label_of(0).unwrap()],
+            RegionStats {
+                area: 4,
+                perimeter: 10,
+                sides: 4,
+                corners: 4
+            }
+        );
+        assert_eq!(
+            stats[regions.label_of(6).unwrap()],
+            RegionStats {
+                area: 4,
+                perimeter: 10,
+                sides: 8,
+                corners: 8
+            }
+        );
+    }
+
+    #[test]
+    fn holes_count_inner_sides() {
+        let grid = Grid::<char>::parse_lines("OOO\nOXO\nOOO");
+        let regions = grid.label_regions();
+        let outer = regions.stats()[regions.label_of(0).unwrap()];
+
+        assert_eq!(outer.area, 8);
+        assert_eq!(outer.perimeter, 16);
+        assert_eq!(outer.sides, 8);
+        assert_eq!(outer.corners, 8);
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    io,
+};
+
+use crate::{
+    template::{ANSI_BOLD, ANSI_RESET},
+    Grid,
+};
+
+impl<T> Display for Grid<T>
+where
+    char: From<T>,
+    T: Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row, cells) in self.rows().enumerate() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+
+            for t in cells {
+                write!(f, "{}", char::from(*t))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders a [`Grid`] as text with optional overlays, per-cell ANSI styles
+/// and row/column rulers. Later overlays and styles replace earlier ones on
+/// the same cell.
+pub struct GridRenderer<'a, T> {
+    grid: &'a Grid<T>,
+    to_char: Box<dyn Fn(&T) -> char + 'a>,
+    overlays: HashMap<usize, char>,
+    styles: HashMap<usize, &'a str>,
+    rulers: bool,
+}
+
+impl<'a, T> GridRenderer<'a, T> {
+    /// Draw the given character over each of the cells
+    pub fn overlay(
+        mut self,
+        cells: impl IntoIterator<Item = usize>,
+        c: char,
+    ) -> Self {
+        self.overlays.extend(cells.into_iter().map(|i| (i, c)));
+        self
+    }
+
+    /// Draw a path of neighboring cells as arrows pointing along the path.
+    /// The final cell is left showing its own content
+    pub fn overlay_path(mut self, path: &[usize]) -> Self {
+        for pair in path.windows(2) {
+            let arrow = self
+                .grid
+                .neighbors(pair[0])
+                .find(|(_, j)| *j == pair[1])
+                .map(|(dir, _)| dir.arrow())
+                .unwrap_or('*');
+
+            self.overlays.insert(pair[0], arrow);
+        }
+
+        self
+    }
+
+    /// Wrap each of the cells in the given ANSI style
+    pub fn highlight(
+        mut self,
+        cells: impl IntoIterator<Item = usize>,
+        style: &'a str,
+    ) -> Self {
+        self.styles.extend(cells.into_iter().map(|i| (i, style)));
+        self
+    }
+
+    /// Style cells based on their index and content
+    pub fn style_with(
+        mut self,
+        style: impl Fn(usize, &T) -> Option<&'a str>,
+    ) -> Self {
+        let grid = self.grid;
+
+        self.styles.extend(
+            grid.data
+                .iter()
+                .enumerate()
+                .filter_map(|(i, t)| style(i, t).map(|s| (i, s))),
+        );
+        self
+    }
+
+    /// Print column numbers above and row numbers beside the grid
+    pub fn rulers(mut self, rulers: bool) -> Self {
+        self.rulers = rulers;
+        self
+    }
+
+    pub fn write_to(&self, w: &mut impl io::Write) -> io::Result<()> {
+        writeln!(w, "{self}")
+    }
+
+    pub fn print(&self) {
+        println!("{self}");
+    }
+
+    fn write_column_rulers(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        margin: usize,
+    ) -> fmt::Result {
+        let digits = (self.grid.width.max(1) - 1).to_string().len();
+
+        for place in (0..digits).rev() {
+            write!(f, "{ANSI_BOLD}{:margin$}", "")?;
+
+            for col in 0..self.grid.width {
+                let digit = col / 10usize.pow(place as u32) % 10;
+
+                if place > 0 && col < 10usize.pow(place as u32) {
+                    write!(f, " ")?;
+                } else {
+                    write!(f, "{digit}")?;
+                }
+            }
+
+            writeln!(f, "{ANSI_RESET}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Display for GridRenderer<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label_width = (self.grid.height.max(1) - 1).to_string().len();
+        let margin = if self.rulers { label_width + 1 } else { 0 };
+
+        if self.rulers {
+            self.write_column_rulers(f, margin)?;
+        }
+
+        for (row, cells) in self.grid.rows().enumerate() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+
+            if self.rulers {
+                write!(f, "{ANSI_BOLD}{row:>label_width$}{ANSI_RESET} ")?;
+            }
+
+            for (col, t) in cells.iter().enumerate() {
+                let i = row * self.grid.width + col;
+                let c = self
+                    .overlays
+                    .get(&i)
+                    .copied()
+                    .unwrap_or_else(|| (self.to_char)(t));
+
+                match self.styles.get(&i) {
+                    Some(style) => write!(f, "{style}{c}{ANSI_RESET}")?,
+                    None => write!(f, "{c}")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Start rendering this grid using `to_char` to draw each cell
+    pub fn renderer<'a>(
+        &'a self,
+        to_char: impl Fn(&T) -> char + 'a,
+    ) -> GridRenderer<'a, T> {
+        GridRenderer {
+            grid: self,
+            to_char: Box::new(to_char),
+            overlays: HashMap::new(),
+            styles: HashMap::new(),
+            rulers: false,
+        }
+    }
+}
+
+impl<T> Grid<T>
+where
+    char: From<T>,
+    T: Copy,
+{
+    /// Start rendering this grid drawing each cell as its own character
+    pub fn render(&self) -> GridRenderer<'_, T> {
+        self.renderer(|t| char::from(*t))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{
+        template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_REVERSE},
+        Grid,
+    };
+
+    #[test]
+    fn displays_grids() {
+        assert_eq!(
+            Grid::<char>::parse_lines("....\n.##.\n....").to_string(),
+            "....\n.##.\n...."
+        );
+        assert_eq!(Grid::<char>::new(vec![], 3).to_string(), "");
+    }
+
+    #[test]
+    fn overlays_paths_and_styles() {
+        let grid = Grid::<char>::parse_lines("....\n.##.\n....");
+
+        assert_eq!(
+            grid.render().overlay_path(&[0, 1, 2, 3, 7, 11]).to_string(),
+            ">>>v\n.##v\n...."
+        );
+        assert_eq!(
+            grid.render()
+                .overlay([5, 6], 'O')
+                .overlay_path(&[8, 11])
+                .to_string(),
+            "....\n.OO.\n*..."
+        );
+        assert_eq!(
+            grid.render().highlight([5], ANSI_RED).to_string(),
+            format!("....\n.{ANSI_RED}#{ANSI_RESET}#.\n....")
+        );
+        assert_eq!(
+            grid.render()
+                .highlight([0, 6], ANSI_GREEN)
+                .style_with(|i, c| (*c == '#' && i > 5).then_some(ANSI_REVERSE))
+                .to_string(),
+            format!(
+                "{ANSI_GREEN}.{ANSI_RESET}...\n\
+                 .#{ANSI_REVERSE}#{ANSI_RESET}.\n...."
+            )
+        );
+    }
+
+    #[test]
+    fn aligns_rulers() {
+        let grid = Grid::new(vec!['.'; 12 * 11], 12);
+        let mut out = vec![];
+
+        grid.render().rulers(true).write_to(&mut out).unwrap();
+
+        let text = String::from_utf8(out)
+            .unwrap()
+            .replace(ANSI_BOLD, "")
+            .replace(ANSI_RESET, "");
+        let lines = text.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "             11");
+        assert_eq!(lines[1], "   012345678901");
+        assert_eq!(lines[2], " 0 ............");
+        assert_eq!(lines[12], "10 ............");
+        assert!(text.ends_with('\n'));
+    }
+}
@@ -0,0 +1,262 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, digit1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    multi::many1,
+    sequence::{pair, terminated},
+    IResult,
+};
+
+use crate::{ws, Grid, GridParseError};
+
+/// An error from one section of a [`Sections`] input. Section numbers are
+/// 1-based like the line numbers in [`GridParseError`], which count from the
+/// start of the section rather than the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionError<E> {
+    Missing { section: usize },
+    Invalid { section: usize, error: E },
+}
+
+impl<E> Display for SectionError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SectionError::Missing { section } => {
+                write!(f, "input has no section {section}")
+            }
+            SectionError::Invalid { section, error } => {
+                write!(f, "section {section}: {error}")
+            }
+        }
+    }
+}
+
+impl<E> Error for SectionError<E> where E: Debug + Display {}
+
+/// The error for a section that isn't a list of numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotANumberList {
+    pub found: String,
+}
+
+impl Error for NotANumberList {}
+
+impl Display for NotANumberList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a list of numbers but found {:?}", self.found)
+    }
+}
+
+/// Numbers separated by whitespace and/or commas
+fn number_list<N: FromStr>(input: &str) -> IResult<&str, Vec<N>> {
+    let number = recognize(pair(opt(char('-')), digit1));
+
+    all_consuming(many1(terminated(
+        ws(map_res(number, N::from_str)),
+        opt(ws(char(','))),
+    )))(input)
+}
+
+/// An input made of blank-line-separated sections, read one at a time in
+/// order as whatever type each one holds. Only truly empty lines separate
+/// sections, and only line endings are trimmed, so grids keep any cells that
+/// are spaces
+#[derive(Debug, Clone)]
+pub struct Sections<'a> {
+    sections: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> Sections<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let mut sections = vec![];
+        let mut start = None;
+        let mut offset = 0;
+
+        for line in input.split_inclusive('\n') {
+            let blank = line.trim_end_matches(['\r', '\n']).is_empty();
+
+            match (start, blank) {
+                (None, false) => start = Some(offset),
+                (Some(s), true) => {
+                    sections
+                        .push(input[s..offset].trim_end_matches(['\r', '\n']));
+                    start = None;
+                }
+                _ => {}
+            }
+
+            offset += line.len();
+        }
+
+        if let Some(s) = start {
+            sections.push(input[s..].trim_end_matches(['\r', '\n']));
+        }
+
+        Self { sections, next: 0 }
+    }
+
+    /// The total number of sections, including ones already read
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// True once every section has been read
+    pub fn is_done(&self) -> bool {
+        self.next == self.sections.len()
+    }
+
+    /// Read the next section as raw text. This can only fail by running out
+    /// of sections
+    pub fn text(&mut self) -> Result<&'a str, SectionError<Infallible>> {
+        self.parse(Ok)
+    }
+
+    /// Read the next section and convert it, tagging any error with the
+    /// section it came from
+    pub fn parse<T, E>(
+        &mut self,
+        parser: impl FnOnce(&'a str) -> Result<T, E>,
+    ) -> Result<T, SectionError<E>> {
+        let section = self.next + 1;
+        let text = self
+            .sections
+            .get(self.next)
+            .ok_or(SectionError::Missing { section })?;
+
+        self.next += 1;
+        parser(text).map_err(|error| SectionError::Invalid { section, error })
+    }
+
+    pub fn grid<T: TryFrom<char>>(
+        &mut self,
+    ) -> Result<Grid<T>, SectionError<GridParseError<T::Error>>> {
+        self.parse(Grid::try_parse)
+    }
+
+    /// Every remaining section as a grid
+    pub fn grids<T: TryFrom<char>>(
+        &mut self,
+    ) -> Result<Vec<Grid<T>>, SectionError<GridParseError<T::Error>>> {
+        let mut grids = vec![];
+
+        while !self.is_done() {
+            grids.push(self.grid()?);
+        }
+
+        Ok(grids)
+    }
+
+    pub fn lines(&mut self) -> Result<Vec<&'a str>, SectionError<Infallible>> {
+        self.text().map(|text| text.lines().collect())
+    }
+
+    /// Read the next section as numbers separated by whitespace, commas or
+    /// newlines
+    pub fn numbers<N: FromStr>(
+        &mut self,
+    ) -> Result<Vec<N>, SectionError<NotANumberList>> {
+        self.parse(|text| {
+            number_list(text).map(|(_, numbers)| numbers).map_err(|_| {
+                NotANumberList {
+                    found: text.to_string(),
+                }
+            })
+        })
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: TryFrom<char>,
+{
+    /// Parse several blank-line-separated grids
+    pub fn try_parse_many(
+        input: &str,
+    ) -> Result<Vec<Self>, SectionError<GridParseError<T::Error>>> {
+        Sections::new(input).grids()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: Display,
+{
+    pub fn parse_many(input: &str) -> Vec<Self> {
+        Self::try_parse_many(input).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Compass, Grid, GridParseError, SectionError, Sections};
+
+    #[test]
+    fn parses_grid_then_moves() {
+        let input = "#####\r\n#@.O#\r\n#####\r\n\r\n\r\n<>^v\r\nvv<<\r\n";
+        let mut sections = Sections::new(input);
+
+        assert_eq!(sections.len(), 2);
+
+        let grid = sections.grid::<char>().unwrap();
+        assert_eq!((grid.width, grid.height), (5, 3));
+
+        let moves = sections
+            .lines()
+            .unwrap()
+            .concat()
+            .chars()
+            .filter_map(Compass::from_arrow)
+            .collect::<Vec<_>>();
+        assert_eq!(moves.len(), 8);
+        assert!(sections.is_done());
+    }
+
+    #[test]
+    fn keeps_spaces_in_grids() {
+        let mut sections = Sections::new("  #\n#  \n   \n\nabc\n");
+
+        let grid = sections.grid::<char>().unwrap();
+        assert_eq!((grid.width, grid.height), (3, 3));
+        assert_eq!(grid.data.iter().filter(|c| **c == ' ').count(), 7);
+        assert_eq!(sections.text(), Ok("abc"));
+    }
+
+    #[test]
+    fn reports_failing_section() {
+        let grids = Grid::<char>::parse_many("#.\n.#\n\n..\n..\n\n##\n");
+        assert_eq!(grids.len(), 3);
+
+        assert_eq!(
+            Grid::<char>::try_parse_many("#.\n.#\n\n...\n..\n"),
+            Err(SectionError::Invalid {
+                section: 2,
+                error: GridParseError::RaggedLine {
+                    line: 2,
+                    expected: 3,
+                    found: 2
+                }
+            })
+        );
+
+        let mut sections = Sections::new("1, 2 ,-3\n4\n\nabc");
+        assert_eq!(sections.numbers::<i32>().unwrap(), vec![1, 2, -3, 4]);
+        assert!(matches!(
+            sections.numbers::<i32>(),
+            Err(SectionError::Invalid { section: 2, .. })
+        ));
+        assert_eq!(sections.text(), Err(SectionError::Missing { section: 3 }));
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use strum::IntoEnumIterator;
+
+use crate::{Compass, Compass8, Grid, Point};
+
+/// An unbounded grid that only stores occupied cells, keyed by signed
+/// coordinates. Useful for worlds that grow as the puzzle runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    pub cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, p: Point) -> Option<&'_ T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&'_ mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'_ T)> + '_ {
+        self.cells.iter().map(|(p, t)| (*p, t))
+    }
+
+    /// The smallest and largest corners of the box containing every occupied
+    /// cell, or `None` if the grid is empty. Finding these visits every cell,
+    /// so get them once before testing many points with [`Point::is_border_of`]
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.cells.keys().fold(None, |acc, p| {
+            let (min, max) = acc.unwrap_or((*p, *p));
+            Some((
+                Point::new(min.col.min(p.col), min.row.min(p.row)),
+                Point::new(max.col.max(p.col), max.row.max(p.row)),
+            ))
+        })
+    }
+
+    /// Every neighboring coordinate whether or not it's occupied
+    pub fn neighbors(
+        &self,
+        p: Point,
+    ) -> impl Iterator<Item = (Compass, Point)> + '_ {
+        Compass::iter().map(move |dir| (dir, p + dir))
+    }
+
+    pub fn neighbors8(
+        &self,
+        p: Point,
+    ) -> impl Iterator<Item = (Compass8, Point)> + '_ {
+        Compass8::iter().map(move |dir| (dir, p + dir))
+    }
+
+    /// Neighboring coordinates that hold a value
+    pub fn occupied_neighbors(
+        &self,
+        p: Point,
+    ) -> impl Iterator<Item = (Compass, Point, &'_ T)> + '_ {
+        self.neighbors(p)
+            .filter_map(|(dir, q)| self.get(q).map(|t| (dir, q, t)))
+    }
+
+    /// Every coordinate on the edge of the bounding box
+    pub fn border(&self) -> impl Iterator<Item = Point> {
+        let bounds = self.bounds();
+
+        bounds.into_iter().flat_map(|(min, max)| {
+            let top_bottom = (min.col..=max.col).flat_map(move |col| {
+                [Point::new(col, min.row), Point::new(col, max.row)]
+            });
+            let sides = (min.row..=max.row).flat_map(move |row| {
+                [Point::new(min.col, row), Point::new(max.col, row)]
+            });
+
+            top_bottom.chain(sides)
+        })
+    }
+}
+
+impl<T> SparseGrid<T>
+where
+    T: Clone,
+{
+    /// Copy the bounding box into a dense grid with `fill` in the empty cells.
+    /// Also returns the coordinate of the dense grid's top-left cell
+    pub fn to_grid(&self, fill: T) -> Option<(Grid<T>, Point)> {
+        let (min, max) = self.bounds()?;
+        let width = (max.col - min.col + 1) as usize;
+        let height = (max.row - min.row + 1) as usize;
+
+        let mut grid = Grid::new(vec![fill; width * height], width);
+
+        for (p, t) in self.iter() {
+            let i = grid.index_of(p - min).unwrap();
+            grid.data[i] = t.clone();
+        }
+
+        Some((grid, min))
+    }
+
+    /// Take every cell of a dense grid that matches `keep`, with the grid's
+    /// top-left cell at the origin
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self {
+        let cells = grid
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| keep(t))
+            .map(|(i, t)| (grid.point_of(i), t.clone()))
+            .collect();
+
+        Self { cells }
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        let width = grid.width;
+        let cells = grid
+            .data
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| {
+                (Point::new((i % width) as i32, (i / width) as i32), t)
+            })
+            .collect();
+
+        Self { cells }
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Displays the bounding box with empty cells shown as `.`
+impl<T> Display for SparseGrid<T>
+where
+    char: From<T>,
+    T: Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+
+        for row in min.row..=max.row {
+            if row > min.row {
+                writeln!(f)?;
+            }
+
+            for col in min.col..=max.col {
+                let c = self
+                    .get(Point::new(col, row))
+                    .map(|t| char::from(*t))
+                    .unwrap_or('.');
+
+                write!(f, "{c}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> SparseGrid<T>
+where
+    char: From<T>,
+    T: Copy,
+{
+    pub fn print(&self) {
+        println!("{self}");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Grid, Point, SparseGrid};
+
+    #[test]
+    fn round_trips_through_dense_grids() {
+        let grid = Grid::<char>::parse_lines(".#.\n..#\n#..");
+        let sparse = SparseGrid::from_grid(&grid, |c| *c == '#');
+
+        assert_eq!(sparse.len(), 3);
+        assert_eq!(sparse.to_string(), ".#.\n..#\n#..");
+        assert_eq!(sparse.to_grid('.'), Some((grid, Point::new(0, 0))));
+    }
+
+    #[test]
+    fn bounds_follow_the_cells() {
+        let mut sparse = SparseGrid::new();
+        assert_eq!(sparse.bounds(), None);
+        assert_eq!(sparse.to_grid('.'), None);
+        assert_eq!(sparse.to_string(), "");
+
+        sparse.insert(Point::new(-2, 1), '#');
+        sparse.insert(Point::new(1, -1), '@');
+        let bounds = sparse.bounds().unwrap();
+        assert_eq!(bounds, (Point::new(-2, -1), Point::new(1, 1)));
+        assert_eq!(sparse.to_string(), "...@\n....\n#...");
+
+        let (dense, origin) = sparse.to_grid(' ').unwrap();
+        assert_eq!((dense.width, dense.height), (4, 3));
+        assert_eq!(origin, Point::new(-2, -1));
+
+        assert!(Point::new(0, -1).is_border_of(bounds));
+        assert!(!Point::new(0, 0).is_border_of(bounds));
+        assert!(!Point::new(100, -1).is_border_of(bounds));
+        assert!(!Point::new(-2, 7).is_border_of(bounds));
+        assert_eq!(sparse.border().count(), 14);
+
+        sparse.remove(Point::new(1, -1));
+        assert_eq!(
+            sparse.bounds(),
+            Some((Point::new(-2, 1), Point::new(-2, 1)))
+        );
+    }
+}
@@ -9,6 +9,9 @@ pub mod runner;
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_REVERSE: &str = "\x1b[7m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
 pub fn read_extra_example_file(day: Day, number: u32) -> String {
     let cwd = env::current_dir().unwrap();
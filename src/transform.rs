@@ -0,0 +1,362 @@
+use crate::Grid;
+
+/// Wraps coordinates into a half-open rectangle. Every tile of a view adds
+/// one of these, kept in the coordinates of the view holding it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Wrap {
+    lo: [i64; 2],
+    size: [i64; 2],
+}
+
+impl Wrap {
+    fn apply(&self, v: [i64; 2]) -> [i64; 2] {
+        [0, 1]
+            .map(|a| self.lo[a] + (v[a] - self.lo[a]).rem_euclid(self.size[a]))
+    }
+
+    /// The same wrap expressed in the coordinates of a remapped view. See
+    /// [`GridView::remap`] for the meaning of the arguments
+    fn remap(
+        &self,
+        origin: (i64, i64),
+        col_step: (i64, i64),
+        row_step: (i64, i64),
+    ) -> Self {
+        let mut result = *self;
+
+        for (a, o) in [origin.0, origin.1].into_iter().enumerate() {
+            let along = |step: (i64, i64)| [step.0, step.1][a];
+
+            // Which new axis runs along this one, and in which direction
+            let (b, sign) = match along(col_step) {
+                0 => (1, along(row_step)),
+                sign => (0, sign),
+            };
+
+            result.size[b] = self.size[a];
+            result.lo[b] = if sign > 0 {
+                self.lo[a] - o
+            } else {
+                o - self.lo[a] - self.size[a] + 1
+            };
+        }
+
+        result
+    }
+}
+
+/// A zero-copy window onto a [`Grid`] that remaps coordinates through any
+/// combination of rotations, flips, sub-grid selection and tiling. View
+/// coordinates are wrapped into each tiled unit, most recent tile first, and
+/// then mapped affinely onto the source grid
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    pub width: usize,
+    pub height: usize,
+    origin: (i64, i64),
+    col_step: (i64, i64),
+    row_step: (i64, i64),
+    wraps: Vec<Wrap>,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            wraps: self.wraps.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    fn new(grid: &'a Grid<T>) -> Self {
+        Self {
+            grid,
+            width: grid.width,
+            height: grid.height,
+            origin: (0, 0),
+            col_step: (1, 0),
+            row_step: (0, 1),
+            wraps: vec![],
+        }
+    }
+
+    /// Map a view coordinate to an unwrapped source coordinate
+    fn project(&self, col: i64, row: i64) -> (i64, i64) {
+        (
+            self.origin.0 + col * self.col_step.0 + row * self.row_step.0,
+            self.origin.1 + col * self.col_step.1 + row * self.row_step.1,
+        )
+    }
+
+    /// Build a new view of size `width` x `height` where the new view's
+    /// `(0, 0)` is at `origin` in this view's coordinates and single steps
+    /// along the new view's columns and rows move by `col_step` and
+    /// `row_step` in this view's coordinates
+    fn remap(
+        self,
+        width: usize,
+        height: usize,
+        origin: (i64, i64),
+        col_step: (i64, i64),
+        row_step: (i64, i64),
+    ) -> Self {
+        let step = |(dc, dr): (i64, i64)| {
+            (
+                dc * self.col_step.0 + dr * self.row_step.0,
+                dc * self.col_step.1 + dr * self.row_step.1,
+            )
+        };
+
+        let wraps = self
+            .wraps
+            .iter()
+            .map(|w| w.remap(origin, col_step, row_step))
+            .collect();
+
+        Self {
+            width,
+            height,
+            origin: self.project(origin.0, origin.1),
+            col_step: step(col_step),
+            row_step: step(row_step),
+            wraps,
+            ..self
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The index in the underlying grid's data of the cell at the given view
+    /// coordinate
+    pub fn source_index_of(&self, col: usize, row: usize) -> usize {
+        let [col, row] = self
+            .wraps
+            .iter()
+            .rev()
+            .fold([col as i64, row as i64], |v, w| w.apply(v));
+        let (c, r) = self.project(col, row);
+
+        c as usize + r as usize * self.grid.width
+    }
+
+    /// The index in the underlying grid's data of the cell at the given flat
+    /// view index
+    pub fn source_index(&self, i: usize) -> usize {
+        self.source_index_of(i % self.width, i / self.width)
+    }
+
+    pub fn get(&self, col: usize, row: usize) -> Option<&'a T> {
+        (col < self.width && row < self.height)
+            .then(|| &self.grid.data[self.source_index_of(col, row)])
+    }
+
+    pub fn at_index(&self, i: usize) -> Option<&'a T> {
+        (i < self.len()).then(|| &self.grid.data[self.source_index(i)])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        (0..self.len()).map(|i| &self.grid.data[self.source_index(i)])
+    }
+
+    pub fn rows(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &'a T> + '_> + '_ {
+        (0..self.height).map(move |row| {
+            (0..self.width)
+                .map(move |col| &self.grid.data[self.source_index_of(col, row)])
+        })
+    }
+
+    pub fn rotate_cw(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.remap(h, w, (0, h as i64 - 1), (0, -1), (1, 0))
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.remap(h, w, (w as i64 - 1, 0), (0, 1), (-1, 0))
+    }
+
+    pub fn rotate_180(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.remap(w, h, (w as i64 - 1, h as i64 - 1), (-1, 0), (0, -1))
+    }
+
+    /// Swap rows and columns (mirror across the main diagonal)
+    pub fn transpose(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.remap(h, w, (0, 0), (0, 1), (1, 0))
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.remap(w, h, (w as i64 - 1, 0), (-1, 0), (0, 1))
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.remap(w, h, (0, h as i64 - 1), (1, 0), (0, -1))
+    }
+
+    /// A `width` x `height` window starting at the given column and row
+    pub fn sub_view(
+        self,
+        col: usize,
+        row: usize,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        assert!(col + width <= self.width && row + height <= self.height);
+
+        self.remap(width, height, (col as i64, row as i64), (1, 0), (0, 1))
+    }
+
+    /// Repeat this view `cols` times horizontally and `rows` times
+    /// vertically. The repeating unit is exactly this view, even if it was
+    /// cut from an earlier tiling across one of its seams
+    pub fn tile(mut self, cols: usize, rows: usize) -> Self {
+        if !self.is_empty() {
+            self.wraps.push(Wrap {
+                lo: [0, 0],
+                size: [self.width as i64, self.height as i64],
+            });
+        }
+
+        Self {
+            width: self.width * cols,
+            height: self.height * rows,
+            ..self
+        }
+    }
+}
+
+impl<T> GridView<'_, T>
+where
+    T: Clone,
+{
+    /// Copy the contents of this view into a new owned grid
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::new(self.iter().cloned().collect(), self.width)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView::new(self)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    pub fn rotate_cw(&self) -> Self {
+        self.view().rotate_cw().to_grid()
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        self.view().rotate_ccw().to_grid()
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.view().rotate_180().to_grid()
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.view().transpose().to_grid()
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        self.view().flip_horizontal().to_grid()
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        self.view().flip_vertical().to_grid()
+    }
+
+    pub fn sub_grid(
+        &self,
+        col: usize,
+        row: usize,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        self.view().sub_view(col, row, width, height).to_grid()
+    }
+
+    pub fn tile(&self, cols: usize, rows: usize) -> Self {
+        self.view().tile(cols, rows).to_grid()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::Grid;
+
+    #[test]
+    fn rotations() {
+        let grid = Grid::<char>::parse_lines("abc\ndef");
+
+        assert_eq!(grid.rotate_cw(), Grid::parse_lines("da\neb\nfc"));
+        assert_eq!(grid.rotate_ccw(), Grid::parse_lines("cf\nbe\nad"));
+        assert_eq!(grid.rotate_180(), Grid::parse_lines("fed\ncba"));
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.rotate_180());
+        assert_eq!(grid.transpose(), Grid::parse_lines("ad\nbe\ncf"));
+        assert_eq!(grid.flip_horizontal(), Grid::parse_lines("cba\nfed"));
+        assert_eq!(grid.flip_vertical(), Grid::parse_lines("def\nabc"));
+    }
+
+    #[test]
+    fn tiles_of_views() {
+        let grid = Grid::<char>::parse_lines("abc\ndef");
+
+        assert_eq!(grid.clone().quadruple(), grid.tile(2, 2));
+        assert_eq!(
+            grid.view()
+                .sub_view(1, 0, 2, 2)
+                .rotate_cw()
+                .tile(2, 1)
+                .to_grid(),
+            Grid::parse_lines("ebeb\nfcfc")
+        );
+        assert_eq!(
+            grid.view().tile(2, 1).sub_view(2, 0, 3, 1).to_grid(),
+            Grid::parse_lines("cab")
+        );
+    }
+
+    #[test]
+    fn tiles_across_seams() {
+        let grid = Grid::<char>::parse_lines("abc\ndef");
+        let seam = grid.view().tile(2, 1).sub_view(2, 0, 2, 1);
+
+        assert_eq!(seam.to_grid(), Grid::parse_lines("ca"));
+        assert_eq!(
+            seam.clone().tile(2, 1).to_grid(),
+            Grid::parse_lines("caca")
+        );
+        assert_eq!(
+            seam.tile(2, 2).rotate_cw().sub_view(0, 1, 2, 2).to_grid(),
+            Grid::parse_lines("aa\ncc")
+        );
+        assert_eq!(
+            grid.view()
+                .tile(3, 3)
+                .flip_horizontal()
+                .sub_view(2, 1, 2, 2)
+                .tile(2, 1)
+                .to_grid(),
+            Grid::parse_lines("dfdf\nacac")
+        );
+    }
+}
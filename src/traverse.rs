@@ -0,0 +1,123 @@
+use crate::Grid;
+
+impl<T> Grid<T> {
+    /// Mutable references to every cell grouped by column, gathered by
+    /// striding down the rows so each column reads top to bottom
+    fn cols_mut(&mut self) -> Vec<Vec<&'_ mut T>> {
+        let (width, height) = (self.width, self.height);
+        let mut cols = (0..width)
+            .map(|_| Vec::with_capacity(height))
+            .collect::<Vec<_>>();
+
+        for row in self.data.chunks_mut(width) {
+            for (col, t) in cols.iter_mut().zip(row) {
+                col.push(t);
+            }
+        }
+
+        cols
+    }
+
+    /// Call `action` on every run of `k` adjacent rows from top to bottom.
+    /// Does nothing if the grid has fewer than `k` rows
+    pub fn for_row_windows_mut<F>(&mut self, k: usize, mut action: F)
+    where
+        F: FnMut(&mut [&'_ mut [T]]),
+    {
+        let mut rows = self.rows_mut().collect::<Vec<_>>();
+
+        for start in windows(rows.len(), k) {
+            action(&mut rows[start..start + k]);
+        }
+    }
+
+    /// Call `action` on every run of `k` adjacent columns from left to
+    /// right. Each column holds its cells from top to bottom. Does nothing if
+    /// the grid has no cells
+    pub fn for_col_windows_mut<F>(&mut self, k: usize, mut action: F)
+    where
+        F: FnMut(&mut [Vec<&'_ mut T>]),
+    {
+        if self.width == 0 || self.data.is_empty() {
+            return;
+        }
+
+        let mut cols = self.cols_mut();
+
+        for start in windows(cols.len(), k) {
+            action(&mut cols[start..start + k]);
+        }
+    }
+
+    /// Column counterpart to [`Grid::for_row_pairs_mut`], calling `action`
+    /// with each column and the one to its right
+    pub fn for_col_pairs_mut<F>(&mut self, mut action: F)
+    where
+        F: FnMut(&mut [&'_ mut T], &mut [&'_ mut T]),
+    {
+        self.for_col_windows_mut(2, |pair| {
+            let (left, right) = pair.split_at_mut(1);
+            action(&mut left[0], &mut right[0]);
+        });
+    }
+}
+
+/// Start positions of every window of `k` items out of `len`
+fn windows(len: usize, k: usize) -> std::ops::Range<usize> {
+    if k == 0 {
+        0..0
+    } else {
+        0..(len + 1).saturating_sub(k)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::Grid;
+
+    #[test]
+    fn empty_grids_are_skipped() {
+        let mut grid = Grid::<u8>::new(vec![], 3);
+        let mut calls = 0;
+
+        grid.for_row_pairs_mut(|_, _| calls += 1);
+        grid.for_col_pairs_mut(|_, _| calls += 1);
+        grid.for_row_windows_mut(3, |_| calls += 1);
+
+        let mut zero_width = Grid {
+            width: 0,
+            height: 0,
+            data: vec![1],
+        };
+        zero_width.for_col_windows_mut(1, |_| calls += 1);
+
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn propagates_across_rows_and_cols() {
+        let mut grid = Grid::new(vec![1; 12], 4);
+
+        grid.for_col_pairs_mut(|left, right| {
+            for (l, r) in left.iter().zip(right.iter_mut()) {
+                **r += **l;
+            }
+        });
+        assert_eq!(grid.data[..4], [1, 2, 3, 4]);
+
+        grid.for_row_windows_mut(3, |rows| {
+            for col in 0..rows[1].len() {
+                rows[1][col] = rows[0][col] + rows[2][col];
+            }
+        });
+        assert_eq!(grid.data[4..8], [2, 4, 6, 8]);
+
+        let mut windows = 0;
+        grid.for_col_windows_mut(3, |cols| {
+            assert_eq!(cols.len(), 3);
+            assert_eq!(cols[0].len(), 3);
+            windows += 1;
+        });
+        assert_eq!(windows, 2);
+    }
+}
@@ -0,0 +1,205 @@
+use itertools::Itertools;
+
+use crate::{Compass, DirSet, Grid, Turn};
+
+/// How a walk ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WalkOutcome {
+    /// The walker stepped off the edge of the grid
+    Exit,
+    /// The walker came back to a cell while facing a heading it already had
+    /// there, so it will repeat the same moves forever
+    Loop,
+}
+
+/// The record of a finished walk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Walk {
+    /// Every position and heading the walker had, in order. Turning in place
+    /// adds a new entry for the same cell
+    pub trail: Vec<(usize, Compass)>,
+    pub outcome: WalkOutcome,
+}
+
+impl Walk {
+    pub fn is_loop(&self) -> bool {
+        self.outcome == WalkOutcome::Loop
+    }
+
+    /// The distinct cells on the trail in the order they were first reached
+    pub fn cells(&self) -> impl Iterator<Item = usize> + '_ {
+        self.trail.iter().map(|(i, _)| *i).unique()
+    }
+}
+
+/// An agent with a position and heading that moves across a [`Grid`],
+/// turning whenever the cell ahead is blocked
+#[derive(Debug)]
+pub struct Walker<'a, T> {
+    grid: &'a Grid<T>,
+    pub pos: usize,
+    pub heading: Compass,
+    obstacle: Option<usize>,
+}
+
+impl<T> Clone for Walker<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Walker<'_, T> {}
+
+impl<'a, T> Walker<'a, T> {
+    pub fn new(grid: &'a Grid<T>, pos: usize, heading: Compass) -> Self {
+        Self {
+            grid,
+            pos,
+            heading,
+            obstacle: None,
+        }
+    }
+
+    /// Treat cell `i` as blocked on top of whatever the rule says, without
+    /// having to copy and edit the grid
+    pub fn with_obstacle(self, i: usize) -> Self {
+        Self {
+            obstacle: Some(i),
+            ..self
+        }
+    }
+
+    fn is_blocked(&self, i: usize, blocked: &impl Fn(&T) -> bool) -> bool {
+        self.obstacle == Some(i) || blocked(&self.grid.data[i])
+    }
+
+    /// Make a single move: turn if the cell ahead is blocked, otherwise step
+    /// into it. Returns false if the move would leave the grid
+    pub fn advance(
+        &mut self,
+        blocked: &impl Fn(&T) -> bool,
+        turn: Turn,
+    ) -> bool {
+        let Some(next) = self.grid.step_from_index(self.pos, self.heading)
+        else {
+            return false;
+        };
+
+        if self.is_blocked(next, blocked) {
+            self.heading = self.heading.turn(turn);
+        } else {
+            self.pos = next;
+        }
+
+        true
+    }
+
+    /// Keep moving until the walker leaves the grid or starts repeating
+    /// itself
+    pub fn walk(mut self, blocked: impl Fn(&T) -> bool, turn: Turn) -> Walk {
+        let mut seen = Grid::new(
+            vec![DirSet::EMPTY; self.grid.data.len()],
+            self.grid.width,
+        );
+        let mut trail = vec![];
+
+        loop {
+            if !seen.data[self.pos].insert(self.heading) {
+                return Walk {
+                    trail,
+                    outcome: WalkOutcome::Loop,
+                };
+            }
+
+            trail.push((self.pos, self.heading));
+
+            if !self.advance(&blocked, turn) {
+                return Walk {
+                    trail,
+                    outcome: WalkOutcome::Exit,
+                };
+            }
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn walker(&self, pos: usize, heading: Compass) -> Walker<'_, T> {
+        Walker::new(self, pos, heading)
+    }
+
+    /// Every cell where adding a single obstacle traps the walker in a loop.
+    /// Only cells on the original trail can change the walk, and each one is
+    /// tried by restarting from the state just before it's first entered
+    /// rather than from the start
+    pub fn loop_obstacles(
+        &self,
+        start: usize,
+        heading: Compass,
+        blocked: impl Fn(&T) -> bool,
+        turn: Turn,
+    ) -> Vec<usize> {
+        let walk = self.walker(start, heading).walk(&blocked, turn);
+        let mut tried = vec![false; self.data.len()];
+        tried[start] = true;
+
+        walk.trail
+            .windows(2)
+            .filter_map(|pair| {
+                let ((pos, heading), (next, _)) = (pair[0], pair[1]);
+
+                if tried[next] {
+                    return None;
+                }
+
+                tried[next] = true;
+
+                self.walker(pos, heading)
+                    .with_obstacle(next)
+                    .walk(&blocked, turn)
+                    .is_loop()
+                    .then_some(next)
+            })
+            .collect()
+    }
+}
+
+/// A guard patrolling a small lab, used by the walker and jump table tests
+#[cfg(feature = "test_lib")]
+pub(crate) const LAB: &str = "....#.....\n.........#\n..........\n..#.......\n\
+                              .......#..\n..........\n.#..^.....\n........#.\n\
+                              #.........\n......#...";
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{Compass, Grid, Turn};
+
+    use super::LAB;
+
+    #[test]
+    fn patrol_exits() {
+        let grid = Grid::<char>::parse_lines(LAB);
+        let start = grid.data.iter().position(|c| *c == '^').unwrap();
+        let walk = grid
+            .walker(start, Compass::N)
+            .walk(|c| *c == '#', Turn::Right);
+
+        assert!(!walk.is_loop());
+        assert_eq!(walk.cells().count(), 41);
+    }
+
+    #[test]
+    fn finds_loop_obstacles() {
+        let grid = Grid::<char>::parse_lines(LAB);
+        let start = grid.data.iter().position(|c| *c == '^').unwrap();
+        let blocks =
+            grid.loop_obstacles(start, Compass::N, |c| *c == '#', Turn::Right);
+
+        assert_eq!(blocks.len(), 6);
+
+        let boxed = Grid::<char>::parse_lines(".#.\n#^#\n.#.");
+        let walk = boxed.walker(4, Compass::N).walk(|c| *c == '#', Turn::Left);
+        assert!(walk.is_loop());
+        assert_eq!(walk.trail.len(), 4);
+    }
+}
@@ -0,0 +1,185 @@
+use strum::IntoEnumIterator;
+
+use crate::{Compass, Compass8, Grid, Point};
+
+/// A toroidal view of a [`Grid`] where stepping off one edge comes back in on
+/// the opposite edge
+#[derive(Debug)]
+pub struct WrappingView<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Clone for WrappingView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WrappingView<'_, T> {}
+
+impl<'a, T> WrappingView<'a, T> {
+    /// Bring any point back onto the grid by taking its coordinates modulo the
+    /// grid's width and height
+    pub fn wrap_point(&self, p: Point) -> Point {
+        Point::new(
+            p.col.rem_euclid(self.grid.width as i32),
+            p.row.rem_euclid(self.grid.height as i32),
+        )
+    }
+
+    pub fn index_of(&self, p: Point) -> usize {
+        let p = self.wrap_point(p);
+        p.col as usize + p.row as usize * self.grid.width
+    }
+
+    pub fn get(&self, p: Point) -> &'a T {
+        &self.grid.data[self.index_of(p)]
+    }
+
+    pub fn step_from_index(&self, i: usize, dir: Compass) -> usize {
+        self.index_of(self.grid.point_of(i) + dir)
+    }
+
+    pub fn step8_from_index(&self, i: usize, dir: Compass8) -> usize {
+        self.index_of(self.grid.point_of(i) + dir)
+    }
+
+    pub fn neighbors(
+        &self,
+        i: usize,
+    ) -> impl Iterator<Item = (Compass, usize)> + '_ {
+        Compass::iter().map(move |dir| (dir, self.step_from_index(i, dir)))
+    }
+
+    pub fn neighbors8(
+        &self,
+        i: usize,
+    ) -> impl Iterator<Item = (Compass8, usize)> + '_ {
+        Compass8::iter().map(move |dir| (dir, self.step8_from_index(i, dir)))
+    }
+}
+
+/// A view of a [`Grid`] repeated infinitely in every direction. Any signed
+/// coordinate maps back onto the base grid along with the coordinate of the
+/// tile copy it falls in, where the base grid itself is tile `(0, 0)`
+#[derive(Debug)]
+pub struct InfiniteView<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Clone for InfiniteView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for InfiniteView<'_, T> {}
+
+impl<'a, T> InfiniteView<'a, T> {
+    /// Split a point into the index of the base grid cell it corresponds to
+    /// and the tile it's in
+    pub fn locate(&self, p: Point) -> (usize, Point) {
+        let (w, h) = (self.grid.width as i32, self.grid.height as i32);
+        let tile = Point::new(p.col.div_euclid(w), p.row.div_euclid(h));
+        let base = p - Point::new(tile.col * w, tile.row * h);
+
+        (
+            base.col as usize + base.row as usize * self.grid.width,
+            tile,
+        )
+    }
+
+    pub fn tile_of(&self, p: Point) -> Point {
+        self.locate(p).1
+    }
+
+    pub fn get(&self, p: Point) -> &'a T {
+        &self.grid.data[self.locate(p).0]
+    }
+
+    pub fn neighbors(
+        &self,
+        p: Point,
+    ) -> impl Iterator<Item = (Compass, Point)> + '_ {
+        Compass::iter().map(move |dir| (dir, p + dir))
+    }
+
+    pub fn neighbors8(
+        &self,
+        p: Point,
+    ) -> impl Iterator<Item = (Compass8, Point)> + '_ {
+        Compass8::iter().map(move |dir| (dir, p + dir))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn wrapping(&self) -> WrappingView<'_, T> {
+        WrappingView { grid: self }
+    }
+
+    pub fn infinite(&self) -> InfiniteView<'_, T> {
+        InfiniteView { grid: self }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{Grid, Point};
+
+    #[test]
+    fn wraps_negative_coordinates() {
+        let grid = Grid::<char>::parse_lines("abc\ndef\nghi");
+        let wrapping = grid.wrapping();
+
+        assert_eq!(wrapping.wrap_point(Point::new(-1, -1)), Point::new(2, 2));
+        assert_eq!(*wrapping.get(Point::new(-1, -1)), 'i');
+        assert_eq!(*wrapping.get(Point::new(-4, 4)), 'f');
+        assert_eq!(*wrapping.get(Point::new(5, -3)), 'c');
+    }
+
+    #[test]
+    fn neighbors_wrap_at_every_edge() {
+        let grid = Grid::<char>::parse_lines("abc\ndef\nghi");
+        let wrapping = grid.wrapping();
+        let around = |i| {
+            wrapping
+                .neighbors(i)
+                .map(|(_, j)| grid.data[j])
+                .collect::<String>()
+        };
+
+        assert_eq!(around(0), "gbdc");
+        assert_eq!(around(8), "fgch");
+        assert_eq!(
+            wrapping
+                .neighbors8(4)
+                .map(|(_, j)| j)
+                .chain(wrapping.neighbors8(0).map(|(_, j)| j))
+                .collect::<HashSet<_>>()
+                .len(),
+            9
+        );
+        assert_eq!(wrapping.neighbors8(0).filter(|(_, j)| *j == 0).count(), 0);
+    }
+
+    #[test]
+    fn locates_negative_tiles() {
+        let grid = Grid::<char>::parse_lines("abc\ndef\nghi");
+        let infinite = grid.infinite();
+
+        assert_eq!(
+            infinite.locate(Point::new(-1, -1)),
+            (8, Point::new(-1, -1))
+        );
+        assert_eq!(
+            infinite.locate(Point::new(-3, -3)),
+            (0, Point::new(-1, -1))
+        );
+        assert_eq!(infinite.locate(Point::new(-4, 0)), (2, Point::new(-2, 0)));
+        assert_eq!(infinite.tile_of(Point::new(2, 2)), Point::new(0, 0));
+        assert_eq!(infinite.tile_of(Point::new(3, -4)), Point::new(1, -2));
+        assert_eq!(*infinite.get(Point::new(-7, 5)), 'i');
+    }
+}